@@ -59,9 +59,17 @@ create_config! {
         "Maximum length of comments. No effect unless wrap_comments = true";
     normalize_comments: bool, false, false, "Convert /* */ comments to // comments where possible";
     normalize_doc_attributes: bool, false, false, "Normalize doc attributes as doc comments";
+    doc_comment_leading_space: bool, false, false,
+        "Put a space after `///` and `//!` in doc comments written without one";
+    javadoc_block_comments: bool, false, false,
+        "Start every line of multi-line block comments with a `*` aligned with the opening one";
     license_template_path: String, String::default(), false,
         "Beginning of file must match license template";
     format_strings: bool, false, false, "Format string literals where necessary";
+    hex_literal_case: LiteralCase, LiteralCase::Upper, false,
+        "Letter case of the digits in hexadecimal literals";
+    float_exponent_case: LiteralCase, LiteralCase::Lower, false,
+        "Letter case of the exponent marker in float literals";
     format_macro_matchers: bool, false, false,
         "Format the metavariable matching patterns in macros";
     format_macro_bodies: bool, true, false, "Format the bodies of macros";
@@ -72,7 +80,13 @@ create_config! {
     struct_lit_single_line: bool, true, false,
         "Put small struct literals on a single line";
     fn_single_line: bool, false, false, "Put single-expression functions on a single line";
+    fn_single_match_body: bool, false, false,
+        "Start a function body that is a single match or if expression on the signature line";
     where_single_line: bool, false, false, "Force where-clauses to be on a single line";
+    generic_bounds_style: GenericBoundsStyle, GenericBoundsStyle::Preserve, false,
+        "Move the bounds of the generic params of items to the where clause, or inline";
+    empty_struct_body: EmptyStructBody, EmptyStructBody::Preserve, false,
+        "Write structs with empty braces as unit structs";
 
     // Imports
     imports_indent: IndentStyle, IndentStyle::Block, false, "Indent of imports";
@@ -81,12 +95,18 @@ create_config! {
         "Merge or split imports to the provided granularity";
     group_imports: GroupImportsTactic, GroupImportsTactic::Preserve, false,
         "Controls the strategy for how imports are grouped together";
+    group_reexports: ReexportsGroup, ReexportsGroup::Preserve, false,
+        "Put `pub use` re-exports in their own group before or after private imports";
+    self_imports_layout: SelfImportsLayout, SelfImportsLayout::Preserve, false,
+        "Merge or split imports starting with `self::` regardless of imports_granularity";
     merge_imports: bool, false, false, "(deprecated: use imports_granularity instead)";
 
     // Ordering
     reorder_imports: bool, true, true, "Reorder import and extern crate statements alphabetically";
     reorder_modules: bool, true, true, "Reorder module statements alphabetically in group";
     reorder_impl_items: bool, false, false, "Reorder impl items";
+    reorder_crate_attributes: bool, false, false,
+        "Put the inner attributes of the crate root that change what kind of crate is built first";
 
     // Spaces around punctuation
     type_punctuation_density: TypeDensity, TypeDensity::Wide, false,
@@ -98,6 +118,8 @@ create_config! {
     spaces_around_ranges: bool, false, false, "Put spaces around the  .. and ..= range operators";
     binop_separator: SeparatorPlace, SeparatorPlace::Front, true,
         "Where to put a binary operator when a binary expression goes multiline";
+    bound_separator: SeparatorPlace, SeparatorPlace::Front, false,
+        "Where to put the `+` between generic bounds when they go multiline";
     space_before_fn_sig_paren: bool, false, false,
         "Whether to put a space before the opening paren in function signatures";
 
@@ -106,10 +128,14 @@ create_config! {
     combine_control_expr: bool, true, false, "Combine control expressions with function calls";
     overflow_delimited_expr: bool, false, false,
         "Allow trailing bracket/brace delimited expressions to overflow";
+    chain_first_call_on_head: bool, false, false,
+        "Keep the first method call of a wrapped chain on the same line as the chain's head";
     struct_field_align_threshold: usize, 0, false,
         "Align struct fields if their diffs fits within threshold";
     enum_discrim_align_threshold: usize, 0, false,
         "Align enum variants discrims, if their diffs fit within threshold";
+    enum_variant_fields_indent: IndentStyle, IndentStyle::Block, false,
+        "Indent of the fields of wrapped struct-like enum variants";
     match_arm_blocks: bool, true, false, "Wrap the body of arms in blocks when it does not fit on \
         the same line with the pattern of arms";
     match_arm_leading_pipes: MatchArmLeadingPipe, MatchArmLeadingPipe::Never, true,
@@ -131,6 +157,10 @@ create_config! {
         "Maximum number of blank lines which can be put between items";
     blank_lines_lower_bound: usize, 0, false,
         "Minimum number of blank lines which must be put between items";
+    blank_lines_around_mods: usize, 0, false,
+        "Minimum number of blank lines which must be put between a module and other items";
+    file_edge_blank_lines: FileEdgeBlankLines, FileEdgeBlankLines::Remove, false,
+        "How to handle blank lines at the start and end of a file";
     edition: Edition, Edition::Edition2018, true, "The edition of the parser (RFC 2052)";
     inline_attribute_width: usize, 0, false,
         "Write an item and its attribute on the same line \
@@ -138,7 +168,13 @@ create_config! {
     format_generated_files: bool, false, false, "Format generated files";
     preserve_block_start_blank_lines: bool, false, false, "Preserve blank lines at the start of \
         blocks.";
+    preserve_byte_order_mark: bool, false, false,
+        "Keep the byte order mark at the start of files that have one";
     preserve_closure_block_wrapping: bool, false , false, "Preserve block wrapping around closures";
+    unwrap_match_arm_blocks: bool, true, false,
+        "Unwrap the blocks around match arm bodies that only contain a tail expression";
+    preserve_struct_lit_wrapping: bool, false, false,
+        "Keep struct literals multi-line if they are multi-line in the source";
 
     // Options that can change the source code beyond whitespace/blocks (somewhat linty things)
     merge_derives: bool, true, true, "Merge multiple `#[derive(...)]` into a single one";
@@ -147,6 +183,8 @@ create_config! {
     force_explicit_abi: bool, true, true, "Always print the abi for extern items";
     condense_wildcard_suffixes: bool, false, false, "Replace strings of _ wildcards by a single .. \
                                                      in tuple patterns";
+    normalize_crate_paths: bool, false, false,
+        "Write `::` paths as `crate::` paths in the 2015 edition, where they are equivalent";
 
     // Control options (changes the operation of rustfmt, rather than the formatting)
     required_version: String, env!("CARGO_PKG_VERSION").to_owned(), false,
@@ -161,6 +199,18 @@ create_config! {
          or they are left with trailing whitespaces";
     ignore: IgnoreList, IgnoreList::default(), true,
         "Skip formatting the specified files and directories";
+    mod_macros: ModMacros, ModMacros::default(), false,
+        "Macros whose bodies are searched for module declarations, like `cfg_if!`";
+    include_macro_mods: bool, false, false,
+        "Resolve the modules declared in files pulled in with `include!`";
+    active_cfgs: ActiveCfgs, ActiveCfgs::default(), false,
+        "Skip modules whose `#[cfg(..)]` is false for these cfg options; no modules are skipped \
+         if empty";
+    max_module_depth: usize, 256, false,
+        "Maximum nesting depth of modules, beyond which module resolution fails";
+    module_path_remaps: ModulePathRemaps, ModulePathRemaps::default(), false,
+        "Read the files of modules from another place, as pairs of path prefixes to replace \
+         and their replacements";
 
     // Not user-facing
     file_lines: FileLines, FileLines::all(), false,
@@ -592,33 +642,46 @@ format_code_in_doc_comments = false
 comment_width = 80
 normalize_comments = false
 normalize_doc_attributes = false
+doc_comment_leading_space = false
+javadoc_block_comments = false
 license_template_path = ""
 format_strings = false
+hex_literal_case = "Upper"
+float_exponent_case = "Lower"
 format_macro_matchers = false
 format_macro_bodies = true
 empty_item_single_line = true
 struct_lit_single_line = true
 fn_single_line = false
+fn_single_match_body = false
 where_single_line = false
+generic_bounds_style = "Preserve"
+empty_struct_body = "Preserve"
 imports_indent = "Block"
 imports_layout = "Mixed"
 imports_granularity = "Preserve"
 group_imports = "Preserve"
+group_reexports = "Preserve"
+self_imports_layout = "Preserve"
 reorder_imports = true
 reorder_modules = true
 reorder_impl_items = false
+reorder_crate_attributes = false
 type_punctuation_density = "Wide"
 space_before_colon = false
 space_after_colon = true
 space_around_attr_eq = true
 spaces_around_ranges = false
 binop_separator = "Front"
+bound_separator = "Front"
 space_before_fn_sig_paren = false
 remove_nested_parens = true
 combine_control_expr = true
 overflow_delimited_expr = false
+chain_first_call_on_head = false
 struct_field_align_threshold = 0
 enum_discrim_align_threshold = 0
+enum_variant_fields_indent = "Block"
 match_arm_blocks = true
 match_arm_leading_pipes = "Never"
 force_multiline_blocks = false
@@ -630,22 +693,33 @@ trailing_comma = "Vertical"
 match_block_trailing_comma = false
 blank_lines_upper_bound = 1
 blank_lines_lower_bound = 0
+blank_lines_around_mods = 0
+file_edge_blank_lines = "Remove"
 edition = "2018"
 inline_attribute_width = 0
 format_generated_files = false
 preserve_block_start_blank_lines = false
+preserve_byte_order_mark = false
 preserve_closure_block_wrapping = false
+unwrap_match_arm_blocks = true
+preserve_struct_lit_wrapping = false
 merge_derives = true
 use_try_shorthand = false
 use_field_init_shorthand = false
 force_explicit_abi = true
 condense_wildcard_suffixes = false
+normalize_crate_paths = false
 required_version = "{}"
 unstable_features = false
 hide_parse_errors = false
 error_on_line_overflow = false
 error_on_unformatted = false
 ignore = []
+mod_macros = []
+include_macro_mods = false
+active_cfgs = []
+max_module_depth = 256
+module_path_remaps = []
 "#,
             env!("CARGO_PKG_VERSION")
         );
@@ -1,5 +1,5 @@
 use crate::config::file_lines::FileLines;
-use crate::config::options::{IgnoreList, WidthHeuristics};
+use crate::config::options::{ActiveCfgs, IgnoreList, ModMacros, ModulePathRemaps, WidthHeuristics};
 
 /// Trait for types that can be used in `Config`.
 pub(crate) trait ConfigType: Sized {
@@ -50,6 +50,24 @@ impl ConfigType for IgnoreList {
     }
 }
 
+impl ConfigType for ModMacros {
+    fn doc_hint() -> String {
+        String::from("[<string>,..]")
+    }
+}
+
+impl ConfigType for ActiveCfgs {
+    fn doc_hint() -> String {
+        String::from("[<string>,..]")
+    }
+}
+
+impl ConfigType for ModulePathRemaps {
+    fn doc_hint() -> String {
+        String::from("[[<string>, <string>],..]")
+    }
+}
+
 macro_rules! update_config {
     ($config:ident, ignore = $val:ident, $dir:ident) => {
         $config.ignore.1 = true;
@@ -60,6 +78,14 @@ macro_rules! update_config {
         $config.ignore.2 = old_ignored.merge_into(new_ignored);
     };
 
+    ($config:ident, module_path_remaps = $val:ident, $dir:ident) => {
+        $config.module_path_remaps.1 = true;
+
+        let mut new_remaps = $val;
+        new_remaps.add_prefix($dir);
+        $config.module_path_remaps.2 = new_remaps;
+    };
+
     ($config:ident, merge_imports = $val:ident, $dir:ident) => {
         $config.merge_imports.1 = true;
         $config.merge_imports.2 = $val;
@@ -1,4 +1,5 @@
 use std::collections::{hash_set, HashSet};
+use std::env;
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
@@ -119,6 +120,17 @@ pub enum GroupImportsTactic {
     StdExternalCrate,
 }
 
+#[config_type]
+/// Where to put `pub use` re-exports relative to private imports.
+pub enum ReexportsGroup {
+    /// Keep re-exports where they are among private imports.
+    Preserve,
+    /// Move re-exports into their own group before private imports.
+    First,
+    /// Move re-exports into their own group after private imports.
+    Last,
+}
+
 #[config_type]
 /// How to merge imports.
 pub enum ImportGranularity {
@@ -134,6 +146,39 @@ pub enum ImportGranularity {
     One,
 }
 
+#[config_type]
+/// How to handle blank lines before the first and after the last line of a file.
+pub enum FileEdgeBlankLines {
+    /// Remove all of them.
+    Remove,
+    /// Keep at most one blank line.
+    KeepOne,
+    /// Keep them as they are.
+    Preserve,
+}
+
+impl FileEdgeBlankLines {
+    /// The number of blank lines to write in place of `count` blank lines in the source.
+    pub fn limit(self, count: usize) -> usize {
+        match self {
+            FileEdgeBlankLines::Remove => 0,
+            FileEdgeBlankLines::KeepOne => count.min(1),
+            FileEdgeBlankLines::Preserve => count,
+        }
+    }
+}
+
+#[config_type]
+/// How to lay out imports starting with `self::`.
+pub enum SelfImportsLayout {
+    /// Treat them like any other import, according to `imports_granularity`.
+    Preserve,
+    /// Merge them into a single `use self::{...}` statement.
+    Collapse,
+    /// Use one `use self::...` statement per imported item.
+    Expand,
+}
+
 #[config_type]
 pub enum ReportTactic {
     Always,
@@ -341,6 +386,151 @@ impl FromStr for IgnoreList {
     }
 }
 
+/// A list of macros whose bodies may contain module declarations, given as path suffixes
+/// (e.g. `platform_mods` or `my_crate::platform_mods`).
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModMacros(Vec<String>);
+
+impl fmt::Display for ModMacros {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter()).finish()
+    }
+}
+
+impl ModMacros {
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl FromStr for ModMacros {
+    type Err = &'static str;
+
+    /// Parses a comma-separated list of macro paths.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ModMacros(
+            s.split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect(),
+        ))
+    }
+}
+
+/// A list of `cfg` options that are considered enabled when resolving modules, written like the
+/// arguments of rustc's `--cfg` flag (e.g. `unix` or `target_os = "linux"`).
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActiveCfgs(Vec<String>);
+
+impl fmt::Display for ActiveCfgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter()).finish()
+    }
+}
+
+impl ActiveCfgs {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the `cfg` option `name`, or `name = "value"`, is enabled.
+    pub fn contains(&self, name: &str, value: Option<&str>) -> bool {
+        self.0.iter().any(|cfg| {
+            let mut parts = cfg.splitn(2, '=');
+            let cfg_name = parts.next().unwrap_or("").trim();
+            let cfg_value = parts.next().map(|v| v.trim().trim_matches('"'));
+            cfg_name == name && cfg_value == value
+        })
+    }
+}
+
+impl FromStr for ActiveCfgs {
+    type Err = &'static str;
+
+    /// Parses a comma-separated list of `cfg` options.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ActiveCfgs(
+            s.split(',')
+                .map(str::trim)
+                .filter(|cfg| !cfg.is_empty())
+                .map(String::from)
+                .collect(),
+        ))
+    }
+}
+
+/// Pairs of path prefixes; the files of modules whose path starts with the first prefix are read
+/// from the path where it is replaced by the second one.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModulePathRemaps(Vec<(PathBuf, PathBuf)>);
+
+impl fmt::Display for ModulePathRemaps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter()).finish()
+    }
+}
+
+impl ModulePathRemaps {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Makes the relative prefixes relative to `dir`, the directory of the `rustfmt.toml` they
+    /// were read from.
+    pub fn add_prefix(&mut self, dir: &Path) {
+        for (from, to) in &mut self.0 {
+            *from = dir.join(&*from);
+            *to = dir.join(&*to);
+        }
+    }
+
+    /// Returns `path` with its prefix replaced, or `None` if no prefix matches. When several
+    /// prefixes match, the longest one wins.
+    pub fn remap(&self, path: &Path) -> Option<PathBuf> {
+        // The prefixes read from a `rustfmt.toml` found by searching its parent directories are
+        // absolute, while module paths are usually relative to the working directory.
+        let absolute_path = if path.is_relative() {
+            env::current_dir().ok().map(|dir| dir.join(path))
+        } else {
+            None
+        };
+        self.0
+            .iter()
+            .filter_map(|(from, to)| {
+                let rest = path
+                    .strip_prefix(from)
+                    .ok()
+                    .or_else(|| absolute_path.as_ref()?.strip_prefix(from).ok())?;
+                Some((from, to.join(rest)))
+            })
+            .max_by_key(|(from, _)| from.components().count())
+            .map(|(_, remapped)| remapped)
+    }
+}
+
+impl FromStr for ModulePathRemaps {
+    type Err = &'static str;
+
+    /// Parses a comma-separated list of `from=to` pairs.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let mut parts = pair.splitn(2, '=');
+                match (parts.next(), parts.next()) {
+                    (Some(from), Some(to)) => {
+                        Ok((PathBuf::from(from.trim()), PathBuf::from(to.trim())))
+                    }
+                    _ => Err("Bad module path remap, expected `from=to`"),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(ModulePathRemaps)
+    }
+}
+
 /// Maps client-supplied options to Rustfmt's internals, mostly overriding
 /// values in a config with values from the command line.
 pub trait CliOptions {
@@ -398,6 +588,37 @@ pub enum MatchArmLeadingPipe {
     Preserve,
 }
 
+/// Controls the letter case of hexadecimal digits and float exponents in literals.
+#[config_type]
+pub enum LiteralCase {
+    /// Leave the letter case of literals as it is.
+    Preserve,
+    /// Use uppercase letters, e.g. `0xFF` and `1E10`.
+    Upper,
+    /// Use lowercase letters, e.g. `0xff` and `1e10`.
+    Lower,
+}
+
+/// Controls how struct items without any fields are written.
+#[config_type]
+pub enum EmptyStructBody {
+    /// Leave empty structs as they are.
+    Preserve,
+    /// Rewrite `struct Foo {}` as `struct Foo;`.
+    Unit,
+}
+
+/// Controls where the bounds of the generic params of items are written.
+#[config_type]
+pub enum GenericBoundsStyle {
+    /// Leave the bounds where they are.
+    Preserve,
+    /// Move inline bounds, like `<T: Clone>`, to the where clause.
+    WhereClause,
+    /// Move the predicates of the where clause to the params they bound, like `<T: Clone>`.
+    Inline,
+}
+
 #[cfg(test)]
 mod test {
     use std::path::PathBuf;
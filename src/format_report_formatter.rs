@@ -126,6 +126,6 @@ fn error_kind_to_snippet_annotation_type(error_kind: &ErrorKind) -> AnnotationTy
         | ErrorKind::TrailingWhitespace
         | ErrorKind::LicenseCheck
         | ErrorKind::BadAttr => AnnotationType::Error,
-        ErrorKind::DeprecatedAttr => AnnotationType::Warning,
+        ErrorKind::DeprecatedAttr | ErrorKind::DuplicatePathAttr => AnnotationType::Warning,
     }
 }
@@ -1,21 +1,23 @@
 // High level formatting functions.
 
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 use rustc_ast::{ast, AstLike};
 use rustc_span::symbol;
 
+pub use syntux::parser::ParserError;
 pub(crate) use syntux::session::ParseSess;
 
 use crate::config::{Config, FileName};
 use crate::formatting::{
     comment::{CharClasses, FullCodeCharKind},
     generated::is_generated_file,
-    modules::{FileModMap, Module},
+    modules::{FileModMap, Module, ModuleTree},
     newline_style::apply_newline_style,
     report::NonFormattedRange,
-    syntux::parser::{DirectoryOwnership, Parser, ParserError},
-    utils::{contains_skip, count_newlines},
+    syntux::parser::{DirectoryOwnership, Parser, BYTE_ORDER_MARK},
+    utils::{contains_skip, count_newlines, mk_sp},
     visitor::FmtVisitor,
 };
 use crate::{
@@ -73,6 +75,60 @@ pub(crate) fn format_input_inner(
     })
 }
 
+pub(crate) fn resolve_module_tree_inner(
+    input: Input,
+    config: &Config,
+) -> Result<ModuleTree, OperationError> {
+    rustc_span::with_session_globals(config.edition().into(), || {
+        let main_file = input.file_name();
+        let input_is_stdin = main_file == FileName::Stdin;
+
+        let parse_session = ParseSess::new(config)?;
+        let directory_ownership = input.to_directory_ownership(true);
+        let krate = Parser::parse_crate(input, &parse_session).map_err(|e| {
+            OperationError::ParseError {
+                input: main_file,
+                is_panic: e == ParserError::ParsePanicError,
+            }
+        })?;
+
+        let (_, module_tree) = modules::ModResolver::new(
+            &parse_session,
+            config,
+            directory_ownership.unwrap_or(DirectoryOwnership::UnownedViaBlock),
+            !input_is_stdin,
+        )
+        .visit_crate_with_tree(&krate)?;
+        Ok(module_tree)
+    })
+}
+
+pub(crate) fn find_sub_module_paths_inner(
+    input: Input,
+    config: &Config,
+) -> Result<Vec<PathBuf>, OperationError> {
+    rustc_span::with_session_globals(config.edition().into(), || {
+        let main_file = input.file_name();
+        let parse_session = ParseSess::new(config)?;
+        let directory_ownership = input.to_directory_ownership(true);
+        let krate = Parser::parse_crate(input, &parse_session).map_err(|e| {
+            OperationError::ParseError {
+                input: main_file,
+                is_panic: e == ParserError::ParsePanicError,
+            }
+        })?;
+
+        let paths = modules::ModResolver::new(
+            &parse_session,
+            config,
+            directory_ownership.unwrap_or(DirectoryOwnership::UnownedViaBlock),
+            false,
+        )
+        .find_sub_mod_paths(&krate)?;
+        Ok(paths)
+    })
+}
+
 fn format_project(
     input: Input,
     config: &Config,
@@ -99,6 +155,11 @@ fn format_project(
     } else {
         None
     };
+    // The byte order marks of the other files are recorded when they are parsed.
+    let root_has_byte_order_mark = match input {
+        Input::File(ref file) => Parser::has_byte_order_mark(file),
+        Input::Text(ref text) => text.starts_with(BYTE_ORDER_MARK),
+    };
 
     let krate = match Parser::parse_crate(input, &parse_session) {
         Ok(krate) => krate,
@@ -117,17 +178,32 @@ fn format_project(
 
     let files = modules::ModResolver::new(
         &parse_session,
+        config,
         directory_ownership.unwrap_or(DirectoryOwnership::UnownedViaBlock),
         !input_is_stdin && operation_setting.recursive,
     )
     .visit_crate(&krate)?;
 
+    for &span in files.ignored_path_attrs() {
+        format_report.add_format_error(
+            parse_session.span_to_filename(span),
+            FormatError::from_span(ErrorKind::DuplicatePathAttr, &parse_session, span),
+        );
+    }
+    for (name, reason) in files.skipped_mods() {
+        should_emit_verbose(input_is_stdin, operation_setting.verbosity, || {
+            println!("Skipping `{}` because {}", name, reason)
+        });
+    }
+
     timer = timer.done_parsing();
 
     // Suppress error output if we have to do any further parsing.
     parse_session.set_silent_emitter();
 
     for (path, module) in &files {
+        // Files found through `module_path_remaps` are formatted where they were read from.
+        let path = module.source_file().unwrap_or(path);
         let should_ignore = (!input_is_stdin && parse_session.ignore_file(&path))
             || (!config.format_generated_files()
                 && is_generated_file(&path, original_snippet.as_ref()));
@@ -142,6 +218,11 @@ fn format_project(
         should_emit_verbose(input_is_stdin, operation_setting.verbosity, || {
             println!("Formatting {}", path)
         });
+        let has_byte_order_mark = if module.is_crate_root() {
+            root_has_byte_order_mark
+        } else {
+            module.has_byte_order_mark()
+        };
         format_file(
             &parse_session,
             config,
@@ -151,6 +232,7 @@ fn format_project(
             &format_report,
             &files,
             original_snippet.clone(),
+            has_byte_order_mark,
             is_macro_def,
         )?;
     }
@@ -176,6 +258,7 @@ fn format_file(
     report: &FormatReport,
     file_mod_map: &FileModMap<'_>,
     original_snippet: Option<String>,
+    has_byte_order_mark: bool,
     is_macro_def: bool,
 ) -> Result<(), OperationError> {
     let snippet_provider = parse_session.snippet_provider(module.span);
@@ -190,6 +273,29 @@ fn format_file(
     visitor.skip_context.update_with_attrs(&krate.attrs);
     visitor.last_pos = snippet_provider.start_pos();
     visitor.skip_empty_lines(snippet_provider.end_pos());
+
+    // Blank lines at the start and end of the file are handled as configured, unless the
+    // file has nothing but whitespace.
+    let (leading_blank_lines, trailing_blank_lines) = snippet_provider
+        .span_to_snippet(mk_sp(
+            snippet_provider.start_pos(),
+            snippet_provider.end_pos(),
+        ))
+        .filter(|snippet| !snippet.trim().is_empty())
+        .map_or((0, 0), |snippet| {
+            let file_edge_blank_lines = config.file_edge_blank_lines();
+            // Only count whole blank lines, not the indentation of the first line.
+            let leading = snippet.len() - snippet.trim_start().len();
+            let leading_newlines = count_newlines(&snippet[..leading]);
+            // The last newline ends the last line rather than making a blank one.
+            let trailing = snippet.trim_end().len();
+            let trailing_newlines = count_newlines(&snippet[trailing..]).saturating_sub(1);
+            (
+                file_edge_blank_lines.limit(leading_newlines),
+                file_edge_blank_lines.limit(trailing_newlines),
+            )
+        });
+
     visitor.format_separate_mod(module, snippet_provider.end_pos());
 
     debug_assert_eq!(
@@ -202,6 +308,9 @@ fn format_file(
     // For some reason, the source_map does not include terminating
     // newlines so we must add one on for each file. This is sad.
     visitor.buffer.push('\n');
+    visitor
+        .buffer
+        .insert_str(0, &"\n".repeat(leading_blank_lines));
 
     format_lines(
         &mut visitor.buffer,
@@ -211,6 +320,8 @@ fn format_file(
         report.clone(),
     );
 
+    visitor.buffer.push_str(&"\n".repeat(trailing_blank_lines));
+
     // SourceFile's in the SourceMap will always have Unix-style line endings
     // See: https://github.com/rust-lang/rustfmt/issues/3850
     // So we must check the file system to get the original file value in order
@@ -226,6 +337,12 @@ fn format_file(
     };
     apply_newline_style(config.newline_style(), &mut visitor.buffer, &original_text);
 
+    // The source map strips the byte order mark from the files it loads, so the formatted
+    // text never starts with one.
+    if config.preserve_byte_order_mark() && has_byte_order_mark {
+        visitor.buffer.insert(0, BYTE_ORDER_MARK);
+    }
+
     if visitor.macro_rewrite_failure {
         report.add_macro_format_failure(path.clone());
     }
@@ -1,8 +1,11 @@
 //! Format attributes and meta items.
 
 use rustc_ast::ast;
+use rustc_ast::token::{Nonterminal, Token, TokenKind};
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
 use rustc_ast::AstLike;
 use rustc_span::{symbol::sym, Span, Symbol};
+use thiserror::Error;
 
 use crate::config::{lists::*, IndentStyle};
 use doc_comment::DocCommentFormatter;
@@ -13,9 +16,11 @@ use crate::formatting::{
     },
     expr::{rewrite_literal, span_ends_with_comma},
     lists::{definitive_tactic, itemize_list, write_list, ListFormatting, Separator},
+    macros::{parse_comma_separated_args, MacroArg},
     overflow,
     rewrite::{Rewrite, RewriteContext},
     shape::Shape,
+    spanned::Spanned,
     types::{rewrite_path, PathContext},
     utils::{count_newlines, mk_sp},
 };
@@ -26,14 +31,126 @@ pub(crate) fn contains_name(attrs: &[ast::Attribute], name: Symbol) -> bool {
     attrs.iter().any(|attr| attr.has_name(name))
 }
 
-pub(crate) fn first_attr_value_str_by_name(
-    attrs: &[ast::Attribute],
-    name: Symbol,
-) -> Option<Symbol> {
+/// An `env!` in the value of an attribute, or in the argument of a macro call, refers to an
+/// environment variable that is not set.
+#[derive(Debug, Error)]
+#[error("environment variable `{0}` is not set")]
+pub(crate) struct MissingEnvVar(String);
+
+impl MissingEnvVar {
+    /// Returns the name of the environment variable.
+    pub(crate) fn var(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Returns the value of the first `#[path]` attribute.
+pub(crate) fn path_attr_value(attrs: &[ast::Attribute]) -> Option<Result<String, MissingEnvVar>> {
     attrs
         .iter()
-        .find(|attr| attr.has_name(name))
-        .and_then(|attr| attr.value_str())
+        .find(|attr| attr.has_name(sym::path))
+        .and_then(attr_str_value)
+}
+
+/// Returns the string value of a `#[name = value]` attribute. Besides string literals, values
+/// built with `concat!` and `env!` are evaluated, e.g.,
+/// `#[path = concat!(env!("OUT_DIR"), "/generated.rs")]`.
+pub(crate) fn attr_str_value(attr: &ast::Attribute) -> Option<Result<String, MissingEnvVar>> {
+    if let Some(value) = attr.value_str() {
+        return Some(Ok(value.to_string()));
+    }
+    match attr_value_expr(attr)?.kind {
+        ast::ExprKind::MacCall(ref mac) => match *mac.args {
+            ast::MacArgs::Delimited(_, _, ref tokens) => {
+                eval_str_macro(mac.path.segments.last()?.ident.name, tokens)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the string value of the argument of a macro call like `include!("foo.rs")`. As for
+/// attributes, arguments built with `concat!` and `env!` are evaluated.
+pub(crate) fn mac_str_arg(mac: &ast::MacCall) -> Option<Result<String, MissingEnvVar>> {
+    match *mac.args {
+        ast::MacArgs::Delimited(_, _, ref tokens) => {
+            let trees: Vec<TokenTree> = tokens.trees().collect();
+            eval_str_tokens(&trees)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the expression after `=` in `#[name = value]`, when it is not a literal.
+fn attr_value_expr(attr: &ast::Attribute) -> Option<&ast::Expr> {
+    let token = match attr.kind {
+        ast::AttrKind::Normal(
+            ast::AttrItem {
+                args: ast::MacArgs::Eq(_, ref token),
+                ..
+            },
+            _,
+        ) => token,
+        _ => return None,
+    };
+    match token.kind {
+        TokenKind::Interpolated(ref nt) => match **nt {
+            Nonterminal::NtExpr(ref expr) => Some(expr),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Evaluates a call to `concat!` or `env!` with the given arguments.
+fn eval_str_macro(name: Symbol, args: &TokenStream) -> Option<Result<String, MissingEnvVar>> {
+    let trees: Vec<TokenTree> = args.trees().collect();
+    let args: Vec<&[TokenTree]> = trees
+        .split(|tree| matches!(tree, TokenTree::Token(token) if token.kind == TokenKind::Comma))
+        .filter(|arg| !arg.is_empty())
+        .collect();
+    if name == sym::concat {
+        let mut value = String::new();
+        for arg in args {
+            match eval_str_tokens(arg)? {
+                Ok(s) => value.push_str(&s),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        Some(Ok(value))
+    } else if name == sym::env {
+        match args.as_slice() {
+            [[TokenTree::Token(token)]] => {
+                let var = str_lit(token)?;
+                Some(std::env::var(&var).map_err(|_| MissingEnvVar(var)))
+            }
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Evaluates a string literal, or a call to `concat!` or `env!`, given as tokens.
+fn eval_str_tokens(trees: &[TokenTree]) -> Option<Result<String, MissingEnvVar>> {
+    match trees {
+        [TokenTree::Token(token)] => str_lit(token).map(Ok),
+        [TokenTree::Token(name), TokenTree::Token(bang), TokenTree::Delimited(_, _, args)]
+            if bang.kind == TokenKind::Not =>
+        {
+            let (ident, _) = name.ident()?;
+            eval_str_macro(ident.name, args)
+        }
+        _ => None,
+    }
+}
+
+fn str_lit(token: &Token) -> Option<String> {
+    match ast::Lit::from_token(token).ok()?.kind {
+        ast::LitKind::Str(symbol, _) => Some(symbol.to_string()),
+        _ => None,
+    }
 }
 
 /// Returns attributes on the given statement.
@@ -198,6 +315,134 @@ fn format_derive(
     Some(result)
 }
 
+/// Formats an attribute whose arguments are a token tree that is not a meta item, e.g.,
+/// `#[foo[a, b]]`, `#[foo { a, b }]` or `#[foo(a = b)]`. Only token trees that look
+/// like a comma-separated list are reformatted; anything else is left as is.
+fn rewrite_delimited_attr(
+    attr: &ast::Attribute,
+    context: &RewriteContext<'_>,
+    shape: Shape,
+) -> Option<String> {
+    let item = match attr.kind {
+        ast::AttrKind::Normal(ref item, _) => item,
+        ast::AttrKind::DocComment(..) => return None,
+    };
+    let (delim_span, delim, tokens) = match item.args {
+        ast::MacArgs::Delimited(delim_span, delim, ref tokens) => (delim_span, delim, tokens),
+        _ => return None,
+    };
+    // Braces are padded with spaces, as in struct literals.
+    let (opener, closer) = match delim {
+        ast::MacDelimiter::Parenthesis => ("(", ")"),
+        ast::MacDelimiter::Bracket => ("[", "]"),
+        ast::MacDelimiter::Brace => (" { ", " }"),
+    };
+
+    let prefix = attr_prefix(attr);
+    // 1 = `[`
+    let path_shape = shape.offset_left(prefix.len() + 1)?;
+    let path = rewrite_path(context, PathContext::Type, None, &item.path, path_shape)?;
+    if tokens.is_empty() {
+        return Some(format!(
+            "{}[{}{}{}]",
+            prefix,
+            path,
+            opener.trim_end(),
+            closer.trim_start()
+        ));
+    }
+
+    let (args, has_trailing_comma) = parse_comma_separated_args(context, tokens)?;
+    if args.iter().any(|arg| matches!(arg, MacroArg::Item(..))) {
+        return None;
+    }
+    // Parenthesized arguments that are not a meta item belong to an attribute macro, which may
+    // give any meaning to its tokens. Only format those that look like meta items.
+    if delim == ast::MacDelimiter::Parenthesis && !args.iter().all(is_meta_like_arg) {
+        return None;
+    }
+
+    let header = format!("{}[{}{}", prefix, path, opener);
+    // 1 = `]`
+    let argument_shape = argument_shape(header.len(), closer.len() + 1, false, shape, context)?;
+    let one_line_width = shape
+        .width
+        .checked_sub(header.len() + closer.len() + 1)?
+        .min(context.config.attr_fn_like_width());
+
+    let items = itemize_list(
+        context.snippet_provider,
+        args.iter(),
+        closer.trim_start(),
+        ",",
+        |arg| arg.span().lo(),
+        |arg| arg.span().hi(),
+        |arg| arg.rewrite(context, argument_shape),
+        delim_span.open.hi(),
+        delim_span.close.lo(),
+        false,
+    )
+    .collect::<Vec<_>>();
+    let tactic = definitive_tactic(
+        &items,
+        ListTactic::HorizontalVertical,
+        Separator::Comma,
+        one_line_width,
+    );
+    // Not every attribute macro accepts a trailing comma, so keep it as written.
+    let trailing_separator = if has_trailing_comma {
+        SeparatorTactic::Always
+    } else {
+        SeparatorTactic::Never
+    };
+    let fmt = ListFormatting::new(argument_shape, context.config)
+        .tactic(tactic)
+        .trailing_separator(trailing_separator)
+        .ends_with_newline(false);
+    let item_str = write_list(&items, &fmt)?;
+
+    let nested = context.config.indent_style() == IndentStyle::Block
+        && tactic != DefinitiveListTactic::Horizontal;
+    if nested {
+        Some(format!(
+            "{}[{}{}{}{}{}{}]",
+            prefix,
+            path,
+            opener.trim_end(),
+            argument_shape.indent.to_string_with_newline(context.config),
+            item_str,
+            shape.indent.to_string_with_newline(context.config),
+            closer.trim_start(),
+        ))
+    } else {
+        Some(format!("{}{}{}]", header, item_str, closer))
+    }
+}
+
+/// Whether `arg` looks like a nested meta item: a path or a literal, `path = value` or
+/// `path(..)`. Unlike in meta items, values may be paths as well as literals.
+fn is_meta_like_arg(arg: &MacroArg) -> bool {
+    match arg {
+        MacroArg::Expr(expr) => is_meta_like_expr(expr, true),
+        _ => false,
+    }
+}
+
+fn is_meta_like_expr(expr: &ast::Expr, allow_lit: bool) -> bool {
+    match expr.kind {
+        ast::ExprKind::Path(None, _) => true,
+        ast::ExprKind::Lit(..) => allow_lit,
+        ast::ExprKind::Assign(ref lhs, ref rhs, _) => {
+            is_meta_like_expr(lhs, false)
+                && matches!(rhs.kind, ast::ExprKind::Path(None, _) | ast::ExprKind::Lit(..))
+        }
+        ast::ExprKind::Call(ref callee, ref args) => {
+            is_meta_like_expr(callee, false) && args.iter().all(|arg| is_meta_like_expr(arg, true))
+        }
+        _ => false,
+    }
+}
+
 /// Returns the first group of attributes that fills the given predicate.
 /// We consider two doc comments are in different group if they are separated by normal comments.
 fn take_while_with_pred<'a, P>(
@@ -378,7 +623,10 @@ impl Rewrite for ast::Attribute {
                         .map_or_else(|| snippet.to_owned(), |rw| format!("{}[{}]", prefix, rw)),
                 )
             } else {
-                Some(snippet.to_owned())
+                Some(
+                    rewrite_delimited_attr(self, context, shape)
+                        .unwrap_or_else(|| snippet.to_owned()),
+                )
             }
         }
     }
@@ -501,6 +749,79 @@ impl<'a> Rewrite for [ast::Attribute] {
     }
 }
 
+/// The attributes that change what kind of crate is built.
+const CRATE_KIND_ATTRS: [Symbol; 9] = [
+    sym::crate_name,
+    sym::crate_type,
+    sym::no_core,
+    sym::no_std,
+    sym::no_main,
+    sym::no_implicit_prelude,
+    sym::recursion_limit,
+    sym::type_length_limit,
+    sym::windows_subsystem,
+];
+
+fn crate_attr_rank(attr: &ast::Attribute) -> u8 {
+    let name = attr.name_or_empty();
+    if CRATE_KIND_ATTRS.contains(&name) {
+        0
+    } else if name == sym::feature {
+        1
+    } else {
+        2
+    }
+}
+
+/// Rewrites the inner attributes of the crate root with the attributes that change what kind of
+/// crate is built first, then `#![feature(..)]` attributes, then the others. Returns `None` if
+/// the attributes are already in that order, or if comments or doc comments other than the
+/// leading ones are mixed with them, since those could not be kept next to their attribute.
+pub(crate) fn rewrite_reordered_crate_attrs(
+    context: &RewriteContext<'_>,
+    attrs: &[ast::Attribute],
+    shape: Shape,
+) -> Option<String> {
+    // The leading doc comments document the crate and stay on top.
+    let doc_comments_len = attrs.iter().take_while(|attr| attr.is_doc_comment()).count();
+    let (doc_comments, attrs) = attrs.split_at(doc_comments_len);
+    if attrs.iter().any(|attr| attr.is_doc_comment()) {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..attrs.len()).collect();
+    order.sort_by_key(|&i| crate_attr_rank(&attrs[i]));
+    if order.iter().enumerate().all(|(i, &j)| i == j) {
+        return None;
+    }
+    let has_comments = attrs.windows(2).any(|pair| {
+        contains_comment(context.snippet(mk_sp(pair[0].span.hi(), pair[1].span.lo())))
+    });
+    if has_comments {
+        return None;
+    }
+
+    let mut result = String::new();
+    if let Some(last_doc_comment) = doc_comments.last() {
+        let gap = context.snippet(mk_sp(last_doc_comment.span.hi(), attrs[0].span.lo()));
+        if contains_comment(gap) {
+            return None;
+        }
+        result.push_str(&doc_comments.rewrite(context, shape)?);
+        result.push('\n');
+        if count_newlines(gap) > 1 {
+            result.push('\n');
+        }
+        result.push_str(&shape.indent.to_string(context.config));
+    }
+    let rewrites = order
+        .into_iter()
+        .map(|i| attrs[i].rewrite(context, shape))
+        .collect::<Option<Vec<_>>>()?;
+    result.push_str(&rewrites.join(&shape.indent.to_string_with_newline(context.config)));
+    Some(result)
+}
+
 fn attr_prefix(attr: &ast::Attribute) -> &'static str {
     match attr.style {
         ast::AttrStyle::Inner => "#!",
@@ -713,6 +713,33 @@ impl<'a> ChainFormatter for ChainFormatterBlock<'a> {
                 break;
             }
         }
+
+        if context.config.chain_first_call_on_head() && !root_rewrite.contains('\n') {
+            // Keep everything up to and including the first method call on the head line, but
+            // only if all of it fits there.
+            let mut rewrite = root_rewrite.clone();
+            for (i, item) in self.shared.children.iter().enumerate() {
+                if let ChainItemKind::Comment(..) = item.kind {
+                    break;
+                }
+                match shape
+                    .offset_left(rewrite.len())
+                    .and_then(|shape| item.rewrite(context, shape))
+                {
+                    Some(ref item_rewrite) if !item_rewrite.contains('\n') => {
+                        rewrite.push_str(item_rewrite)
+                    }
+                    _ => break,
+                }
+                if let ChainItemKind::MethodCall(..) = item.kind {
+                    root_rewrite = rewrite;
+                    root_ends_with_block = last_line_extendable(&root_rewrite);
+                    self.shared.children = &self.shared.children[i + 1..];
+                    break;
+                }
+            }
+        }
+
         self.shared.rewrites.push(root_rewrite);
         self.root_ends_with_block = root_ends_with_block;
         Some(())
@@ -304,7 +304,13 @@ fn rewrite_closure_fn_decl(
         }
         IndentStyle::Visual => nested_shape.offset_left(1)?.visual_indent(0),
     };
-    let ret_str = fn_decl.output.rewrite(context, param_shape)?;
+    // The return type is laid out relative to the closure itself, so that a wrapped
+    // return type is indented like the parameters rather than one level deeper.
+    let ret_shape = match indent_style {
+        IndentStyle::Block => Shape::indented(shape.indent, context.config),
+        IndentStyle::Visual => param_shape,
+    };
+    let ret_str = fn_decl.output.rewrite(context, ret_shape)?;
 
     let param_items = itemize_list(
         context.snippet_provider,
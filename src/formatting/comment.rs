@@ -251,7 +251,43 @@ pub(crate) fn combine_strs_with_missing_comments(
 
 pub(crate) fn rewrite_doc_comment(orig: &str, shape: Shape, config: &Config) -> Option<String> {
     debug!("rewrite_doc_comment: {:?}", orig);
-    identify_comment(orig, false, shape, config, true)
+    if config.doc_comment_leading_space() {
+        let orig = add_doc_comment_leading_space(orig);
+        identify_comment(&orig, false, shape, config, true)
+    } else {
+        identify_comment(orig, false, shape, config, true)
+    }
+}
+
+/// Inserts a space after `///` or `//!` on doc comment lines that have none, e.g. `///text`.
+/// Lines inside code blocks are kept as they are, and lines that already start with
+/// whitespace keep their indentation.
+fn add_doc_comment_leading_space(orig: &str) -> String {
+    let mut in_code_block = false;
+    orig.split('\n')
+        .map(|line| {
+            let trimmed = line.trim_start();
+            let (indent, rest) = line.split_at(line.len() - trimmed.len());
+            let (opener, content) = match rest.get(..3) {
+                Some(opener @ "///") | Some(opener @ "//!") if !rest.starts_with("////") => {
+                    (opener, &rest[3..])
+                }
+                _ => return line.to_owned(),
+            };
+            let is_fence = content.trim_start().starts_with("```");
+            let needs_space =
+                (!in_code_block || is_fence) && content.starts_with(|c: char| !c.is_whitespace());
+            if is_fence {
+                in_code_block = !in_code_block;
+            }
+            if needs_space {
+                format!("{}{} {}", indent, opener, content)
+            } else {
+                line.to_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 pub(crate) fn rewrite_comment(
@@ -369,24 +405,24 @@ fn identify_comment(
     };
 
     let (first_group, rest) = orig.split_at(first_group_ending);
-    let rewritten_first_group =
-        if !config.normalize_comments() && has_bare_lines && style.is_block_comment() {
-            trim_left_preserve_layout(first_group, shape.indent, config, is_doc_comment)?
-        } else if !config.normalize_comments()
-            && !config.wrap_comments()
-            && !config.format_code_in_doc_comments()
-        {
-            light_rewrite_comment(first_group, shape.indent, config, is_doc_comment)
-        } else {
-            rewrite_comment_inner(
-                first_group,
-                block_style,
-                style,
-                shape,
-                config,
-                is_doc_comment || style.is_doc_comment(),
-            )
-        };
+    // Bare lines of block comments get a leading `*` with `javadoc_block_comments`.
+    let add_line_starts =
+        config.javadoc_block_comments() && has_bare_lines && style.is_block_comment();
+    let keep_layout = !config.normalize_comments() && !add_line_starts;
+    let rewritten_first_group = if keep_layout && has_bare_lines && style.is_block_comment() {
+        trim_left_preserve_layout(first_group, shape.indent, config, is_doc_comment)?
+    } else if keep_layout && !config.wrap_comments() && !config.format_code_in_doc_comments() {
+        light_rewrite_comment(first_group, shape.indent, config, is_doc_comment)
+    } else {
+        rewrite_comment_inner(
+            first_group,
+            block_style,
+            style,
+            shape,
+            config,
+            is_doc_comment || style.is_doc_comment(),
+        )
+    };
     if rest.is_empty() {
         Some(rewritten_first_group)
     } else {
@@ -837,13 +873,15 @@ fn rewrite_comment_inner(
         })
         .map(|s| left_trim_comment_line(s, &style))
         .map(|(line, has_leading_whitespace)| {
+            // Text that follows a `*` added by `javadoc_block_comments` is separated from it.
+            let javadoc_line =
+                config.javadoc_block_comments() && style.is_block_comment() && !line.is_empty();
+            let has_leading_whitespace =
+                has_leading_whitespace || config.normalize_comments() || javadoc_line;
             if orig.starts_with("/*") && line_breaks == 0 {
-                (
-                    line.trim_start(),
-                    has_leading_whitespace || config.normalize_comments(),
-                )
+                (line.trim_start(), has_leading_whitespace)
             } else {
-                (line, has_leading_whitespace || config.normalize_comments())
+                (line, has_leading_whitespace)
             }
         });
 
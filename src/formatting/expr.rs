@@ -6,13 +6,14 @@ use rustc_ast::token::{DelimToken, LitKind};
 use rustc_ast::{ast, ptr};
 use rustc_span::{BytePos, Span};
 
-use crate::config::{lists::*, BraceStyle, Config, ControlBraceStyle, IndentStyle};
+use crate::config::{lists::*, BraceStyle, Config, ControlBraceStyle, IndentStyle, LiteralCase};
 use crate::formatting::{
     chains::rewrite_chain,
     closures,
     comment::{
-        combine_strs_with_missing_comments, contains_comment, recover_comment_removed,
-        rewrite_comment, rewrite_missing_comment, CharClasses, FindUncommented,
+        combine_strs_with_missing_comments, contains_comment, is_last_comment_block,
+        recover_comment_removed, rewrite_comment, rewrite_missing_comment, CharClasses,
+        FindUncommented,
     },
     lists::{
         definitive_tactic, itemize_list, shape_for_tactic, struct_lit_formatting, struct_lit_shape,
@@ -165,7 +166,7 @@ pub(crate) fn format_expr(
                         // Rewrite block without trying to put it in a single line.
                         rw
                     } else {
-                        let prefix = block_prefix(context, block, shape)?;
+                        let prefix = block_prefix(context, block, shape, true)?;
 
                         rewrite_block_with_visitor(
                             context,
@@ -247,7 +248,40 @@ pub(crate) fn format_expr(
         ast::ExprKind::Ret(Some(ref expr)) => {
             rewrite_unary_prefix(context, "return ", &**expr, shape)
         }
-        ast::ExprKind::Box(ref expr) => rewrite_unary_prefix(context, "box ", &**expr, shape),
+        ast::ExprKind::Box(ref subexpr) => {
+            let between_span = mk_sp(
+                context.snippet_provider.span_after(expr.span, "box"),
+                subexpr.span.lo(),
+            );
+            let between_snippet = context.snippet(between_span);
+            if contains_comment(between_snippet) {
+                let comment = between_snippet.trim();
+                // A block comment on the line of `box` keeps the operand right after it.
+                let (subexpr_shape, comment_shape) = if !between_snippet.trim_end().contains('\n')
+                    && comment.starts_with("/*")
+                    && is_last_comment_block(comment)
+                {
+                    // 5 = `box ` and the space after the comment
+                    (shape.offset_left(comment.len() + 5)?, shape)
+                } else {
+                    let nested_shape = shape
+                        .block_indent(context.config.tab_spaces())
+                        .with_max_width(context.config);
+                    (nested_shape, nested_shape)
+                };
+                let subexpr_str = subexpr.rewrite(context, subexpr_shape)?;
+                combine_strs_with_missing_comments(
+                    context,
+                    "box",
+                    &subexpr_str,
+                    between_span,
+                    comment_shape,
+                    true,
+                )
+            } else {
+                rewrite_unary_prefix(context, "box ", &**subexpr, shape)
+            }
+        }
         ast::ExprKind::AddrOf(borrow_kind, mutability, ref expr) => {
             rewrite_expr_addrof(context, borrow_kind, mutability, expr, shape)
         }
@@ -508,9 +542,18 @@ fn rewrite_empty_block(
     None
 }
 
-fn block_prefix(context: &RewriteContext<'_>, block: &ast::Block, shape: Shape) -> Option<String> {
+fn block_prefix(
+    context: &RewriteContext<'_>,
+    block: &ast::Block,
+    shape: Shape,
+    is_statement: bool,
+) -> Option<String> {
     Some(match block.rules {
         ast::BlockCheckMode::Unsafe(..) => {
+            // An `unsafe` block in statement position also follows the control flow brace style.
+            let brace_on_next_line = context.config.brace_style() == BraceStyle::AlwaysNextLine
+                || (is_statement
+                    && context.config.control_brace_style() == ControlBraceStyle::AlwaysNextLine);
             let snippet = context.snippet(block.span);
             let open_pos = snippet.find_uncommented("{")?;
             // Extract comment between unsafe and block start.
@@ -525,22 +568,19 @@ fn block_prefix(context: &RewriteContext<'_>, block: &ast::Block, shape: Shape)
                     Shape::legacy(budget, shape.indent + 7),
                     context.config,
                 )?;
-                match context.config.brace_style() {
-                    BraceStyle::AlwaysNextLine => format!(
+                if brace_on_next_line {
+                    format!(
                         "unsafe {}{}",
                         rewritten_comment,
                         shape.indent.to_string_with_newline(&context.config)
-                    ),
-                    _ => format!("unsafe {} ", rewritten_comment),
+                    )
+                } else {
+                    format!("unsafe {} ", rewritten_comment)
                 }
+            } else if brace_on_next_line {
+                format!("unsafe{}", shape.indent.to_string_with_newline(&context.config))
             } else {
-                match context.config.brace_style() {
-                    BraceStyle::AlwaysNextLine => format!(
-                        "unsafe{}",
-                        shape.indent.to_string_with_newline(&context.config)
-                    ),
-                    _ => "unsafe ".to_owned(),
-                }
+                "unsafe ".to_owned()
             }
         }
         ast::BlockCheckMode::Default => String::new(),
@@ -616,7 +656,7 @@ fn rewrite_block(
     context: &RewriteContext<'_>,
     shape: Shape,
 ) -> Option<String> {
-    let prefix = block_prefix(context, block, shape)?;
+    let prefix = block_prefix(context, block, shape, false)?;
 
     // shape.width is used only for the single line case: either the empty block `{}`,
     // or an unsafe expression `unsafe { e }`.
@@ -1234,6 +1274,7 @@ pub(crate) fn rewrite_literal(
 ) -> Option<String> {
     match l.kind {
         ast::LitKind::Str(_, ast::StrStyle::Cooked) => rewrite_string_lit(context, l.span, shape),
+        ast::LitKind::Int(..) | ast::LitKind::Float(..) => rewrite_numeric_lit(context, l, shape),
         _ => wrap_str(
             context.snippet(l.span).to_owned(),
             context.config.max_width(),
@@ -1267,6 +1308,44 @@ fn rewrite_string_lit(context: &RewriteContext<'_>, span: Span, shape: Shape) ->
     )
 }
 
+fn rewrite_numeric_lit(
+    context: &RewriteContext<'_>,
+    lit: &ast::Lit,
+    shape: Shape,
+) -> Option<String> {
+    let symbol = lit.token.symbol.as_str();
+    let normalized_symbol = match lit.kind {
+        ast::LitKind::Int(..) => symbol.strip_prefix("0x").and_then(|hex_digits| {
+            apply_literal_case(hex_digits, context.config.hex_literal_case())
+                .map(|hex_digits| format!("0x{}", hex_digits))
+        }),
+        // The symbol of a float literal does not include its suffix, so the only letter
+        // that may appear in it is the exponent marker.
+        ast::LitKind::Float(..) => {
+            apply_literal_case(&symbol, context.config.float_exponent_case())
+        }
+        _ => None,
+    };
+    let lit_str = match normalized_symbol {
+        Some(normalized_symbol) => match lit.token.suffix {
+            Some(suffix) => format!("{}{}", normalized_symbol, suffix),
+            None => normalized_symbol,
+        },
+        None => context.snippet(lit.span).to_owned(),
+    };
+
+    wrap_str(lit_str, context.config.max_width(), shape)
+}
+
+/// Returns `None` if the letter case of the literal should be preserved.
+fn apply_literal_case(s: &str, literal_case: LiteralCase) -> Option<String> {
+    match literal_case {
+        LiteralCase::Preserve => None,
+        LiteralCase::Upper => Some(s.to_ascii_uppercase()),
+        LiteralCase::Lower => Some(s.to_ascii_lowercase()),
+    }
+}
+
 fn choose_separator_tactic(context: &RewriteContext<'_>, span: Span) -> Option<SeparatorTactic> {
     if context.inside_macro() {
         if span_ends_with_comma(context, span) {
@@ -1574,9 +1653,16 @@ fn rewrite_struct_lit<'a>(
 
     // Foo { a: Foo } - indent is +3, width is -5.
     let (h_shape, v_shape) = struct_lit_shape(shape, context, path_str.len() + 3, 2)?;
+    let body_lo = context.snippet_provider.span_after(span, "{");
+    let h_shape = if context.config.preserve_struct_lit_wrapping()
+        && context.snippet(mk_sp(body_lo, span.hi())).contains('\n')
+    {
+        None
+    } else {
+        h_shape
+    };
 
     let one_line_width = h_shape.map_or(0, |shape| shape.width);
-    let body_lo = context.snippet_provider.span_after(span, "{");
     let fields_str = if struct_lit_can_be_aligned(fields, has_base)
         && context.config.struct_field_align_threshold() > 0
     {
@@ -226,7 +226,14 @@ pub(crate) fn flatten_use_trees(use_trees: Vec<UseTree>) -> Vec<UseTree> {
     // order is by first occurance of the use-tree.
     use_trees
         .into_iter()
-        .flat_map(UseTree::flatten)
+        .flat_map(|tree| {
+            // Splitting an import with attributes would need a copy of them on every part.
+            if tree.attrs.is_some() {
+                vec![tree]
+            } else {
+                tree.flatten()
+            }
+        })
         .map(|mut tree| {
             // If a path ends in `::self`, rewrite it to `::{self}`.
             if let Some(UseSegment::Slf(..)) = tree.path.last() {
@@ -242,6 +249,22 @@ pub(crate) fn flatten_use_trees(use_trees: Vec<UseTree>) -> Vec<UseTree> {
         .collect()
 }
 
+/// Replaces the use trees starting with `self::` with the result of `f`, placing them where the
+/// first of them used to be.
+pub(crate) fn map_self_use_trees<F>(use_trees: Vec<UseTree>, f: F) -> Vec<UseTree>
+where
+    F: FnOnce(Vec<UseTree>) -> Vec<UseTree>,
+{
+    let first_self_pos = match use_trees.iter().position(UseTree::starts_with_self) {
+        Some(pos) => pos,
+        None => return use_trees,
+    };
+    let (self_trees, mut other_trees): (Vec<_>, Vec<_>) =
+        use_trees.into_iter().partition(UseTree::starts_with_self);
+    other_trees.splice(first_self_pos..first_self_pos, f(self_trees));
+    other_trees
+}
+
 impl fmt::Debug for UseTree {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(self, f)
@@ -397,6 +420,13 @@ impl UseTree {
 
         let leading_modsep =
             context.config.edition() >= Edition::Edition2018 && a.prefix.is_global();
+        // In the 2015 edition, `use ::foo` imports from the crate root like `use crate::foo`.
+        if context.config.normalize_crate_paths()
+            && context.config.edition() == Edition::Edition2015
+            && a.prefix.is_global()
+        {
+            result.path.push(UseSegment::Crate(None));
+        }
 
         let mut modsep = leading_modsep;
 
@@ -563,6 +593,22 @@ impl UseTree {
         self
     }
 
+    fn starts_with_self(&self) -> bool {
+        matches!(self.path.first(), Some(UseSegment::Slf(..)))
+    }
+
+    /// Whether this is a re-export, i.e. a `use` with a visibility other than private.
+    pub(crate) fn is_reexport(&self) -> bool {
+        match self.visibility {
+            Some(ast::Visibility {
+                kind: ast::VisibilityKind::Inherited,
+                ..
+            })
+            | None => false,
+            Some(_) => true,
+        }
+    }
+
     fn has_comment(&self) -> bool {
         self.list_item.as_ref().map_or(false, ListItem::has_comment)
     }
@@ -9,7 +9,7 @@ use rustc_ast::{ast, ptr};
 use rustc_span::{symbol, BytePos, Span, DUMMY_SP};
 
 use crate::config::lists::*;
-use crate::config::{BraceStyle, Config, IndentStyle};
+use crate::config::{BraceStyle, Config, EmptyStructBody, GenericBoundsStyle, IndentStyle};
 use crate::formatting::{
     attr::filter_inline_attrs,
     comment::{
@@ -21,7 +21,7 @@ use crate::formatting::{
         is_empty_block, is_simple_block_stmt, rewrite_assign_rhs, rewrite_assign_rhs_with,
         rewrite_assign_rhs_with_comments, RhsTactics,
     },
-    lists::{definitive_tactic, itemize_list, write_list, ListFormatting, Separator},
+    lists::{definitive_tactic, itemize_list, write_list, ListFormatting, ListItem, Separator},
     macros::{rewrite_macro, MacroPosition},
     overflow,
     reorder::compare_as_versions,
@@ -398,9 +398,20 @@ impl<'a> FmtVisitor<'a> {
     ) -> Option<(String, FnBraceStyle)> {
         let context = self.get_context();
 
-        let mut fn_brace_style = newline_for_brace(self.config, &fn_sig.generics.where_clause);
-        let (result, _, force_newline_brace) =
-            rewrite_fn_base(&context, indent, ident, fn_sig, span, fn_brace_style)?;
+        let moved_generics = move_generic_bounds(&context, fn_sig.generics, span);
+        let where_clause = moved_generics
+            .as_ref()
+            .map_or(&fn_sig.generics.where_clause, |generics| &generics.where_clause);
+        let mut fn_brace_style = newline_for_brace(self.config, where_clause);
+        let (result, _, force_newline_brace) = rewrite_fn_base(
+            &context,
+            indent,
+            ident,
+            fn_sig,
+            moved_generics.as_ref(),
+            span,
+            fn_brace_style,
+        )?;
 
         // 2 = ` {`
         if self.config.brace_style() == BraceStyle::AlwaysNextLine
@@ -430,6 +441,7 @@ impl<'a> FmtVisitor<'a> {
             indent,
             ident,
             &FnSig::from_method_sig(sig, generics, DEFAULT_VISIBILITY),
+            move_generic_bounds(&context, generics, span).as_ref(),
             span,
             FnBraceStyle::None,
         )?;
@@ -480,6 +492,53 @@ impl<'a> FmtVisitor<'a> {
         }
     }
 
+    // Put a function body that consists of a single `match` or `if` expression on the
+    // same line as the signature, so that the expression keeps the function's indentation.
+    pub(crate) fn single_match_fn(
+        &self,
+        fn_str: &str,
+        fn_brace_style: FnBraceStyle,
+        block: &ast::Block,
+        inner_attrs: Option<&[ast::Attribute]>,
+    ) -> Option<String> {
+        if !self.config.fn_single_match_body()
+            || fn_brace_style != FnBraceStyle::SameLine
+            || inner_attrs.map_or(false, |a| !a.is_empty())
+            || block.stmts.len() != 1
+        {
+            return None;
+        }
+
+        let expr = match block.stmts[0].kind {
+            ast::StmtKind::Expr(ref expr) => expr,
+            _ => return None,
+        };
+        if !matches!(expr.kind, ast::ExprKind::Match(..) | ast::ExprKind::If(..))
+            || !expr.attrs.is_empty()
+        {
+            return None;
+        }
+
+        // Comments around the expression have nowhere to go.
+        let context = self.get_context();
+        if contains_comment(context.snippet(mk_sp(block.span.lo(), expr.span.lo())))
+            || contains_comment(context.snippet(mk_sp(expr.span.hi(), block.span.hi())))
+        {
+            return None;
+        }
+
+        // 3 = ` { `, 2 = ` }`
+        let shape = Shape::indented(self.block_indent, self.config)
+            .offset_left(last_line_width(fn_str) + 3)?
+            .sub_width(2)?;
+        let res = expr.rewrite(&context, shape)?;
+        if res.contains('\n') {
+            Some(format!("{} {{ {} }}", fn_str, res))
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn visit_static(&mut self, static_parts: &StaticParts<'_>) {
         let rewrite = rewrite_static(&self.get_context(), static_parts, self.block_indent);
         self.push_rewrite(static_parts.span, rewrite);
@@ -777,7 +836,16 @@ pub(crate) fn format_impl(
             ..
         } = **impl_kind;
         let mut result = String::with_capacity(128);
-        let ref_and_type = format_impl_ref_and_type(context, item, offset)?;
+        let header_span = mk_sp(
+            item.span.lo(),
+            context.snippet_provider.span_before(item.span, "{"),
+        );
+        let moved_generics = move_generic_bounds(context, generics, header_span);
+        let where_clause = moved_generics
+            .as_ref()
+            .map_or(&generics.where_clause, |generics| &generics.where_clause);
+        let ref_and_type =
+            format_impl_ref_and_type(context, item, moved_generics.as_ref(), offset)?;
         let sep = offset.to_string_with_newline(context.config);
         result.push_str(&ref_and_type);
 
@@ -792,19 +860,22 @@ pub(crate) fn format_impl(
         let open_pos = snippet.find_uncommented("{")? + 1;
         if !contains_comment(&snippet[open_pos..])
             && items.is_empty()
-            && generics.where_clause.predicates.len() == 1
+            && where_clause.predicates.len() == 1
             && !result.contains('\n')
         {
             option.suppress_comma();
             option.snuggle();
             option.allow_single_line();
         }
+        if moved_generics.is_some() {
+            option.moved_predicates();
+        }
 
         let missing_span = mk_sp(self_ty.span.hi(), item.span.hi());
         let where_span_end = context.snippet_provider.opt_span_before(missing_span, "{");
         let where_clause_str = rewrite_where_clause(
             context,
-            &generics.where_clause,
+            where_clause,
             context.config.brace_style(),
             Shape::legacy(where_budget, offset.block_only()),
             false,
@@ -816,7 +887,7 @@ pub(crate) fn format_impl(
 
         // If there is no where-clause, we may have missing comments between the trait name and
         // the opening brace.
-        if generics.where_clause.predicates.is_empty() {
+        if where_clause.predicates.is_empty() {
             if let Some(hi) = where_span_end {
                 match recover_missing_comment_in_span(
                     mk_sp(self_ty.span.hi(), hi),
@@ -838,7 +909,7 @@ pub(crate) fn format_impl(
                 // if the where_clause contains extra comments AND
                 // there is only one where-clause predicate
                 // recover the suppressed comma in single line where_clause formatting
-                if generics.where_clause.predicates.len() == 1 {
+                if where_clause.predicates.len() == 1 {
                     result.push(',');
                 }
                 result.push_str(&format!("{}{{{}}}", sep, sep));
@@ -929,6 +1000,7 @@ fn is_impl_single_line(
 fn format_impl_ref_and_type(
     context: &RewriteContext<'_>,
     item: &ast::Item,
+    moved_generics: Option<&ast::Generics>,
     offset: Indent,
 ) -> Option<String> {
     if let ast::ItemKind::Impl(impl_kind) = &item.kind {
@@ -949,7 +1021,8 @@ fn format_impl_ref_and_type(
         result.push_str(format_unsafety(unsafety));
 
         let shape = Shape::indented(offset + last_line_width(&result), context.config);
-        let generics_str = rewrite_generics(context, "impl", generics, shape)?;
+        let generics_str =
+            rewrite_generics_or_moved(context, "impl", generics, moved_generics, shape)?;
         result.push_str(&generics_str);
         result.push_str(format_constness_right(constness));
 
@@ -976,8 +1049,11 @@ fn format_impl_ref_and_type(
             trait_ref_overhead = 0;
         }
 
+        let where_clause = moved_generics.map_or(&generics.where_clause, |generics| {
+            &generics.where_clause
+        });
         // Try to put the self type in a single line.
-        let curly_brace_overhead = if generics.where_clause.predicates.is_empty() {
+        let curly_brace_overhead = if where_clause.predicates.is_empty() {
             // If there is no where-clause adapt budget for type formatting to take space and curly
             // brace into account.
             match context.config.brace_style() {
@@ -1057,6 +1133,7 @@ fn rewrite_trait_ref(
 
 pub(crate) struct StructParts<'a> {
     prefix: &'a str,
+    is_variant: bool,
     ident: symbol::Ident,
     vis: &'a ast::Visibility,
     def: &'a ast::VariantData,
@@ -1072,6 +1149,7 @@ impl<'a> StructParts<'a> {
     fn from_variant(variant: &'a ast::Variant) -> Self {
         StructParts {
             prefix: "",
+            is_variant: true,
             ident: variant.ident,
             vis: &DEFAULT_VISIBILITY,
             def: &variant.data,
@@ -1088,6 +1166,7 @@ impl<'a> StructParts<'a> {
         };
         StructParts {
             prefix,
+            is_variant: false,
             ident: item.ident,
             vis: &item.vis,
             def,
@@ -1103,6 +1182,9 @@ fn format_struct(
     offset: Indent,
     one_line_width: Option<usize>,
 ) -> Option<String> {
+    if let Some(rewrite) = format_empty_struct_body(context, struct_parts, offset) {
+        return Some(rewrite);
+    }
     match *struct_parts.def {
         ast::VariantData::Unit(..) => format_unit_struct(context, struct_parts, offset),
         ast::VariantData::Tuple(ref fields, _) => {
@@ -1133,9 +1215,19 @@ pub(crate) fn format_trait(
 
         let body_lo = context.snippet_provider.span_after(item.span, "{");
 
+        let moved_generics = move_generic_bounds(context, generics, mk_sp(item.span.lo(), body_lo));
+        let where_clause = moved_generics
+            .as_ref()
+            .map_or(&generics.where_clause, |generics| &generics.where_clause);
+
         let shape = Shape::indented(offset, context.config).offset_left(result.len())?;
-        let generics_str =
-            rewrite_generics(context, rewrite_ident(context, item.ident), generics, shape)?;
+        let generics_str = rewrite_generics_or_moved(
+            context,
+            rewrite_ident(context, item.ident),
+            generics,
+            moved_generics.as_ref(),
+            shape,
+        )?;
         result.push_str(&generics_str);
 
         // FIXME(#2055): rustfmt fails to format when there are comments between trait bounds.
@@ -1159,7 +1251,7 @@ pub(crate) fn format_trait(
         }
 
         // Rewrite where-clause.
-        if !generics.where_clause.predicates.is_empty() {
+        if !where_clause.predicates.is_empty() {
             let where_on_new_line = context.config.indent_style() != IndentStyle::Block;
 
             let where_budget = context.budget(last_line_width(&result));
@@ -1168,10 +1260,13 @@ pub(crate) fn format_trait(
             } else {
                 generic_bounds[generic_bounds.len() - 1].span().hi()
             };
-            let option = WhereClauseOption::snuggled(&generics_str);
+            let mut option = WhereClauseOption::snuggled(&generics_str);
+            if moved_generics.is_some() {
+                option.moved_predicates();
+            }
             let where_clause_str = rewrite_where_clause(
                 context,
-                &generics.where_clause,
+                where_clause,
                 context.config.brace_style(),
                 Shape::legacy(where_budget, offset.block_only()),
                 where_on_new_line,
@@ -1229,7 +1324,7 @@ pub(crate) fn format_trait(
             BraceStyle::PreferSameLine => result.push(' '),
             BraceStyle::SameLineWhere => {
                 if result.contains('\n')
-                    || (!generics.where_clause.predicates.is_empty() && !trait_items.is_empty())
+                    || (!where_clause.predicates.is_empty() && !trait_items.is_empty())
                 {
                     result.push_str(&offset.to_string_with_newline(context.config));
                 } else {
@@ -1350,6 +1445,34 @@ pub(crate) fn format_trait_alias(
     rewrite_assign_rhs(context, lhs, &trait_alias_bounds, shape.sub_width(1)?).map(|s| s + ";")
 }
 
+// Rewrite a braced struct item without fields as a unit struct when `empty_struct_body` asks
+// for it. Returns `None` when the struct should be formatted as written: only plain `struct`
+// items without generics, where clauses or comments are converted, never unions or enum
+// variants.
+fn format_empty_struct_body(
+    context: &RewriteContext<'_>,
+    p: &StructParts<'_>,
+    offset: Indent,
+) -> Option<String> {
+    if context.config.empty_struct_body() != EmptyStructBody::Unit || p.is_variant {
+        return None;
+    }
+    match *p.def {
+        ast::VariantData::Struct(ref fields, _) if fields.is_empty() => (),
+        _ => return None,
+    }
+    let generics = p.generics?;
+    if p.prefix != "struct "
+        || !generics.params.is_empty()
+        || !generics.where_clause.predicates.is_empty()
+        || contains_comment(context.snippet(mk_sp(p.ident.span.hi(), p.span.hi())))
+    {
+        return None;
+    }
+
+    Some(format!("{};", p.format_header(context, offset)))
+}
+
 fn format_unit_struct(
     context: &RewriteContext<'_>,
     p: &StructParts<'_>,
@@ -1460,9 +1583,20 @@ pub(crate) fn format_struct_struct(
         && items_str.len() <= one_line_budget
         && !last_line_contains_single_line_comment(&items_str)
     {
-        Some(format!("{} {} }}", result, items_str))
+        return Some(format!("{} {} }}", result, items_str));
+    }
+
+    let visual_items_str = if struct_parts.is_variant
+        && context.config.enum_variant_fields_indent() == IndentStyle::Visual
+    {
+        rewrite_visual_variant_fields(context, &result, fields, offset, mk_sp(body_lo, span.hi()))
     } else {
-        Some(format!(
+        None
+    };
+
+    match visual_items_str {
+        Some(items_str) => Some(format!("{} {} }}", result, items_str)),
+        None => Some(format!(
             "{}\n{}{}\n{}}}",
             result,
             offset
@@ -1470,8 +1604,29 @@ pub(crate) fn format_struct_struct(
                 .to_string(context.config),
             items_str,
             offset.to_string(context.config)
-        ))
+        )),
+    }
+}
+
+// Format the fields of a struct-like enum variant aligned with its opening brace, e.g.,
+// `Variant { a: A,\n          b: B }`. Returns `None` when the fields cannot be written that way.
+fn rewrite_visual_variant_fields(
+    context: &RewriteContext<'_>,
+    header: &str,
+    fields: &[ast::FieldDef],
+    offset: Indent,
+    span: Span,
+) -> Option<String> {
+    // 1 = the space after `{`
+    let used_width = last_line_used_width(header, offset.width()) + 1;
+    let items_indent = offset + used_width.checked_sub(offset.width())?;
+    // 2 = ` }`
+    let shape = Shape::indented(items_indent, context.config).sub_width(2)?;
+    let items_str = rewrite_with_alignment(fields, context, shape, span, 0)?;
+    if last_line_contains_single_line_comment(&items_str) {
+        return None;
     }
+    Some(items_str.strip_suffix(',').unwrap_or(&items_str).to_owned())
 }
 
 fn get_bytepos_after_visibility(vis: &ast::Visibility, default_span: Span) -> BytePos {
@@ -1555,14 +1710,21 @@ fn format_tuple_struct(
         Some(generics) => {
             let budget = context.budget(last_line_width(&header_str));
             let shape = Shape::legacy(budget, offset);
-            let generics_str = rewrite_generics(context, "", generics, shape)?;
+            let moved_generics = move_generic_bounds(context, generics, span);
+            let generics_str =
+                rewrite_generics_or_moved(context, "", generics, moved_generics.as_ref(), shape)?;
             result.push_str(&generics_str);
 
             let where_budget = context.budget(last_line_width(&result));
-            let option = WhereClauseOption::new(true, WhereClauseSpace::Newline);
+            let mut option = WhereClauseOption::new(true, WhereClauseSpace::Newline);
+            if moved_generics.is_some() {
+                option.moved_predicates();
+            }
             rewrite_where_clause(
                 context,
-                &generics.where_clause,
+                moved_generics
+                    .as_ref()
+                    .map_or(&generics.where_clause, |generics| &generics.where_clause),
                 context.config.brace_style(),
                 Shape::legacy(where_budget, offset.block_only()),
                 false,
@@ -1625,6 +1787,10 @@ fn rewrite_type<R: Rewrite>(
     let mut result = String::with_capacity(128);
     result.push_str(&format!("{}type ", format_visibility(context, vis)));
     let ident_str = rewrite_ident(context, ident);
+    let moved_generics = move_generic_bounds(context, generics, span);
+    let where_clause = moved_generics
+        .as_ref()
+        .map_or(&generics.where_clause, |generics| &generics.where_clause);
 
     if generics.params.is_empty() {
         result.push_str(ident_str)
@@ -1633,7 +1799,13 @@ fn rewrite_type<R: Rewrite>(
         let g_shape = Shape::indented(indent, context.config)
             .offset_left(result.len())?
             .sub_width(2)?;
-        let generics_str = rewrite_generics(context, ident_str, generics, g_shape)?;
+        let generics_str = rewrite_generics_or_moved(
+            context,
+            ident_str,
+            generics,
+            moved_generics.as_ref(),
+            g_shape,
+        )?;
         result.push_str(&generics_str);
     }
 
@@ -1651,9 +1823,12 @@ fn rewrite_type<R: Rewrite>(
     if rhs.is_none() {
         option.suppress_comma();
     }
+    if moved_generics.is_some() {
+        option.moved_predicates();
+    }
     let where_clause_str = rewrite_where_clause(
         context,
-        &generics.where_clause,
+        where_clause,
         context.config.brace_style(),
         Shape::legacy(where_budget, indent),
         false,
@@ -1667,7 +1842,7 @@ fn rewrite_type<R: Rewrite>(
     if let Some(ty) = rhs {
         // If there's a where clause, add a newline before the assignment. Otherwise just add a
         // space.
-        let has_where = !generics.where_clause.predicates.is_empty();
+        let has_where = !where_clause.predicates.is_empty();
         if has_where {
             result.push_str(&indent.to_string_with_newline(context.config));
         } else {
@@ -2293,18 +2468,23 @@ pub(crate) enum FnBraceStyle {
     None,
 }
 
+// `moved_generics` are the generics returned by `move_generic_bounds`, which replace those of
+// `fn_sig` when the bounds are moved.
 // Return type is (result, force_new_line_for_brace)
 fn rewrite_fn_base(
     context: &RewriteContext<'_>,
     indent: Indent,
     ident: symbol::Ident,
     fn_sig: &FnSig<'_>,
+    moved_generics: Option<&ast::Generics>,
     span: Span,
     fn_brace_style: FnBraceStyle,
 ) -> Option<(String, bool, bool)> {
     let mut force_new_line_for_brace = false;
 
-    let where_clause = &fn_sig.generics.where_clause;
+    let where_clause = moved_generics.map_or(&fn_sig.generics.where_clause, |generics| {
+        &generics.where_clause
+    });
 
     let mut result = String::with_capacity(1024);
     result.push_str(&fn_sig.to_str(context));
@@ -2328,10 +2508,11 @@ fn rewrite_fn_base(
         offset: used_width,
     };
     let fd = fn_sig.decl;
-    let generics_str = rewrite_generics(
+    let generics_str = rewrite_generics_or_moved(
         context,
         rewrite_ident(context, ident),
         fn_sig.generics,
+        moved_generics,
         shape,
     )?;
     result.push_str(&generics_str);
@@ -2574,6 +2755,9 @@ fn rewrite_fn_base(
     if is_params_multi_lined {
         option.veto_single_line();
     }
+    if moved_generics.is_some() {
+        option.moved_predicates();
+    }
     let where_clause_str = rewrite_where_clause(
         context,
         where_clause,
@@ -2630,6 +2814,7 @@ struct WhereClauseOption {
     snuggle: WhereClauseSpace,
     allow_single_line: bool, // Try single line where-clause instead of vertical layout
     veto_single_line: bool,  // Disallow a single-line where-clause.
+    moved_predicates: bool,  // The predicates come from `move_generic_bounds`.
 }
 
 impl WhereClauseOption {
@@ -2639,6 +2824,7 @@ impl WhereClauseOption {
             snuggle,
             allow_single_line: false,
             veto_single_line: false,
+            moved_predicates: false,
         }
     }
 
@@ -2652,6 +2838,7 @@ impl WhereClauseOption {
             },
             allow_single_line: false,
             veto_single_line: false,
+            moved_predicates: false,
         }
     }
 
@@ -2670,6 +2857,12 @@ impl WhereClauseOption {
     fn veto_single_line(&mut self) {
         self.veto_single_line = true;
     }
+
+    /// The spans of moved predicates do not locate them in the source, so the comments around
+    /// them are not looked for. Bounds are only moved when there are none.
+    fn moved_predicates(&mut self) {
+        self.moved_predicates = true;
+    }
 }
 
 fn rewrite_params(
@@ -2836,6 +3029,171 @@ fn rewrite_generics(
     overflow::rewrite_with_angle_brackets(context, ident, params, shape, generics.span)
 }
 
+/// Returns the generics of an item with the bounds moved as `generic_bounds_style` asks, or
+/// `None` if no bound is moved. The spans of the moved bounds no longer match their position,
+/// so bounds are only moved when there are no comments to keep around them, neither in the
+/// generics nor from there to the end of `span`, which covers the item up to its body.
+fn move_generic_bounds(
+    context: &RewriteContext<'_>,
+    generics: &ast::Generics,
+    span: Span,
+) -> Option<ast::Generics> {
+    if generics.params.is_empty() || context.config.indent_style() != IndentStyle::Block {
+        return None;
+    }
+    // `rewrite_moved_generics` drops the comments in the generics.
+    if contains_comment(context.snippet(generics.span))
+        || contains_comment(context.snippet(mk_sp(generics.span.hi(), span.hi())))
+    {
+        return None;
+    }
+    match context.config.generic_bounds_style() {
+        GenericBoundsStyle::Preserve => None,
+        GenericBoundsStyle::WhereClause => move_bounds_to_where_clause(generics),
+        GenericBoundsStyle::Inline => move_bounds_inline(generics),
+    }
+}
+
+/// Moves the bounds of the generic params to the front of the where clause.
+fn move_bounds_to_where_clause(generics: &ast::Generics) -> Option<ast::Generics> {
+    if generics.params.iter().all(|param| param.bounds.is_empty()) {
+        return None;
+    }
+    let mut moved = generics.clone();
+    let mut predicates = vec![];
+    for param in &mut moved.params {
+        if param.bounds.is_empty() {
+            continue;
+        }
+        let span = param.span();
+        let bounds = std::mem::take(&mut param.bounds);
+        predicates.push(match param.kind {
+            ast::GenericParamKind::Lifetime => {
+                ast::WherePredicate::RegionPredicate(ast::WhereRegionPredicate {
+                    span,
+                    lifetime: ast::Lifetime {
+                        id: ast::NodeId::root(),
+                        ident: param.ident,
+                    },
+                    bounds,
+                })
+            }
+            _ => ast::WherePredicate::BoundPredicate(ast::WhereBoundPredicate {
+                span,
+                bound_generic_params: vec![],
+                bounded_ty: ptr::P(ast::Ty {
+                    id: ast::NodeId::root(),
+                    kind: ast::TyKind::Path(None, ast::Path::from_ident(param.ident)),
+                    span: param.ident.span,
+                    tokens: None,
+                }),
+                bounds,
+            }),
+        });
+    }
+    predicates.append(&mut moved.where_clause.predicates);
+    moved.where_clause.predicates = predicates;
+    Some(moved)
+}
+
+/// Moves the predicates of the where clause to the generic params they bound. Nothing is moved
+/// unless every predicate bounds a generic param without inline bounds, so that bounds like
+/// `?Sized` are never merged with other bounds of the same param, nor split from them.
+fn move_bounds_inline(generics: &ast::Generics) -> Option<ast::Generics> {
+    if generics.where_clause.predicates.is_empty() {
+        return None;
+    }
+    let mut moved = generics.clone();
+    for predicate in &generics.where_clause.predicates {
+        let (ident, bounds) = match predicate {
+            ast::WherePredicate::BoundPredicate(ast::WhereBoundPredicate {
+                bound_generic_params,
+                bounded_ty,
+                bounds,
+                ..
+            }) if bound_generic_params.is_empty() => match bounded_ty.kind {
+                ast::TyKind::Path(None, ref path)
+                    if path.segments.len() == 1 && path.segments[0].args.is_none() =>
+                {
+                    (path.segments[0].ident, bounds)
+                }
+                _ => return None,
+            },
+            ast::WherePredicate::RegionPredicate(ast::WhereRegionPredicate {
+                lifetime,
+                bounds,
+                ..
+            }) => (lifetime.ident, bounds),
+            _ => return None,
+        };
+        let param = moved.params.iter_mut().find(|param| {
+            param.ident.name == ident.name
+                && !matches!(param.kind, ast::GenericParamKind::Const { .. })
+        })?;
+        if !param.bounds.is_empty() {
+            return None;
+        }
+        param.bounds = bounds.clone();
+    }
+    moved.where_clause.predicates.clear();
+    Some(moved)
+}
+
+/// Rewrites `generics`, or `moved_generics` in their place when `move_generic_bounds` returned
+/// some.
+fn rewrite_generics_or_moved(
+    context: &RewriteContext<'_>,
+    ident: &str,
+    generics: &ast::Generics,
+    moved_generics: Option<&ast::Generics>,
+    shape: Shape,
+) -> Option<String> {
+    match moved_generics {
+        Some(generics) => rewrite_moved_generics(context, ident, generics, shape),
+        None => rewrite_generics(context, ident, generics, shape),
+    }
+}
+
+/// Rewrites the generic params returned by `move_generic_bounds`, without using their spans. The
+/// comments in the generics would be lost, so bounds are never moved when there are any.
+fn rewrite_moved_generics(
+    context: &RewriteContext<'_>,
+    ident: &str,
+    generics: &ast::Generics,
+    shape: Shape,
+) -> Option<String> {
+    let nested_shape = Shape::indented(shape.indent.block_indent(context.config), context.config)
+        .sub_width(1)?;
+    let items = generics
+        .params
+        .iter()
+        .map(|param| param.rewrite(context, nested_shape).map(ListItem::from_str))
+        .collect::<Option<Vec<_>>>()?;
+    // 2 = `<>`
+    let one_line_width = shape.width.saturating_sub(ident.len() + 2);
+    let tactic = definitive_tactic(
+        &items,
+        ListTactic::HorizontalVertical,
+        Separator::Comma,
+        one_line_width,
+    );
+    let fmt = ListFormatting::new(nested_shape, context.config)
+        .tactic(tactic)
+        .trailing_separator(context.config.trailing_comma());
+    let params_str = write_list(&items, &fmt)?;
+    if tactic == DefinitiveListTactic::Horizontal {
+        Some(format!("{}<{}>", ident, params_str))
+    } else {
+        Some(format!(
+            "{}<{}{}{}>",
+            ident,
+            nested_shape.indent.to_string_with_newline(context.config),
+            params_str,
+            shape.indent.to_string_with_newline(context.config)
+        ))
+    }
+}
+
 fn rewrite_where_clause_rfc_style(
     context: &RewriteContext<'_>,
     where_clause: &ast::WhereClause,
@@ -2908,10 +3266,13 @@ fn rewrite_where_keyword(
         }
     };
 
-    let (span_before, span_after) =
-        missing_span_before_after_where(span_end_before_where, where_clause);
-    let (comment_before, comment_after) =
-        rewrite_comments_before_after_where(context, span_before, span_after, shape)?;
+    let (comment_before, comment_after) = if where_clause_option.moved_predicates {
+        (String::new(), String::new())
+    } else {
+        let (span_before, span_after) =
+            missing_span_before_after_where(span_end_before_where, where_clause);
+        rewrite_comments_before_after_where(context, span_before, span_after, shape)?
+    };
 
     let starting_newline = match where_clause_option.snuggle {
         WhereClauseSpace::Space if comment_before.is_empty() => Cow::from(" "),
@@ -2942,6 +3303,20 @@ fn rewrite_bounds_on_where_clause(
     where_clause_option: WhereClauseOption,
     force_single_line: bool,
 ) -> Option<String> {
+    if where_clause_option.moved_predicates {
+        let items = where_clause
+            .predicates
+            .iter()
+            .map(|pred| pred.rewrite(context, shape).map(ListItem::from_str))
+            .collect::<Option<Vec<_>>>()?;
+        return write_where_predicates(
+            context,
+            &items,
+            shape,
+            where_clause_option,
+            force_single_line,
+        );
+    }
     let span_start = where_clause.predicates[0].span().lo();
     // If we don't have the start of the next span, then use the end of the
     // predicates, but that means we miss comments.
@@ -2960,6 +3335,23 @@ fn rewrite_bounds_on_where_clause(
         span_end,
         false,
     );
+    write_where_predicates(
+        context,
+        &items.collect::<Vec<_>>(),
+        shape,
+        where_clause_option,
+        force_single_line,
+    )
+}
+
+/// Writes the predicates of a where clause, one per line unless `force_single_line` is set.
+fn write_where_predicates(
+    context: &RewriteContext<'_>,
+    items: &[ListItem],
+    shape: Shape,
+    where_clause_option: WhereClauseOption,
+    force_single_line: bool,
+) -> Option<String> {
     let comma_tactic = if where_clause_option.suppress_comma || force_single_line {
         SeparatorTactic::Never
     } else {
@@ -2978,7 +3370,7 @@ fn rewrite_bounds_on_where_clause(
         .tactic(shape_tactic)
         .trailing_separator(comma_tactic)
         .preserve_newline(true);
-    write_list(&items.collect::<Vec<_>>(), &fmt)
+    write_list(items, &fmt)
 }
 
 fn rewrite_where_clause(
@@ -3159,7 +3551,12 @@ fn format_generics(
     used_width: usize,
 ) -> Option<String> {
     let shape = Shape::legacy(context.budget(used_width + offset.width()), offset);
-    let mut result = rewrite_generics(context, "", generics, shape)?;
+    let moved_generics = move_generic_bounds(context, generics, span);
+    let mut result =
+        rewrite_generics_or_moved(context, "", generics, moved_generics.as_ref(), shape)?;
+    let where_clause = moved_generics
+        .as_ref()
+        .map_or(&generics.where_clause, |generics| &generics.where_clause);
 
     // If the generics are not parameterized then generics.span.hi() == 0,
     // so we use span.lo(), which is the position after `struct Foo`.
@@ -3168,15 +3565,18 @@ fn format_generics(
     } else {
         span.lo()
     };
-    let (same_line_brace, missed_comments) = if !generics.where_clause.predicates.is_empty() {
+    let (same_line_brace, missed_comments) = if !where_clause.predicates.is_empty() {
         let budget = context.budget(last_line_used_width(&result, offset.width()));
         let mut option = WhereClauseOption::snuggled(&result);
         if brace_pos == BracePos::None {
             option.suppress_comma = true;
         }
+        if moved_generics.is_some() {
+            option.moved_predicates();
+        }
         let where_clause_str = rewrite_where_clause(
             context,
-            &generics.where_clause,
+            where_clause,
             brace_style,
             Shape::legacy(budget, offset.block_only()),
             true,
@@ -3279,6 +3679,7 @@ impl Rewrite for ast::ForeignItem {
                         shape.indent,
                         self.ident,
                         &FnSig::from_method_sig(&fn_sig, generics, self.vis.clone()),
+                        move_generic_bounds(context, generics, span).as_ref(),
                         span,
                         FnBraceStyle::None,
                     )
@@ -3375,8 +3776,13 @@ pub(crate) fn rewrite_mod(
     item: &ast::Item,
     attrs_shape: Shape,
 ) -> Option<String> {
+    let unsafety = match item.kind {
+        ast::ItemKind::Mod(unsafety, _) => unsafety,
+        _ => unreachable!(),
+    };
     let mut result = String::with_capacity(32);
     result.push_str(&*format_visibility(context, &item.vis));
+    result.push_str(format_unsafety(unsafety));
     result.push_str("mod ");
     result.push_str(rewrite_ident(context, item.ident));
     result.push(';');
@@ -3401,11 +3807,13 @@ pub(crate) fn rewrite_extern_crate(
     rewrite_attrs(context, item, &item_str, attrs_shape)
 }
 
-/// Returns `true` for `mod foo;`, false for `mod foo { .. }`.
+/// Returns `true` for `mod foo;` and `unsafe mod foo;`, false for `mod foo { .. }` and for items
+/// that are not modules.
 pub(crate) fn is_mod_decl(item: &ast::Item) -> bool {
     match item.kind {
         ast::ItemKind::Mod(_, ast::ModKind::Loaded(_, ast::Inline::Yes, _)) => false,
-        _ => true,
+        ast::ItemKind::Mod(..) => true,
+        _ => false,
     }
 }
 
@@ -144,6 +144,31 @@ fn parse_macro_arg<'a, 'b: 'a>(parser: &'a mut Parser<'b>) -> Option<MacroArg> {
     None
 }
 
+/// Parses `tokens` as a list of comma-separated arguments. Returns the arguments
+/// and whether the list ends with a trailing comma, or `None` if the tokens are
+/// not list-like.
+pub(crate) fn parse_comma_separated_args(
+    context: &RewriteContext<'_>,
+    tokens: &TokenStream,
+) -> Option<(Vec<MacroArg>, bool)> {
+    let mut parser = build_parser(context, tokens.trees());
+    let mut args = Vec::new();
+
+    loop {
+        args.push(check_keyword(&mut parser).or_else(|| parse_macro_arg(&mut parser))?);
+
+        match parser.token.kind {
+            TokenKind::Eof => return Some((args, false)),
+            TokenKind::Comma => parser.bump(),
+            _ => return None,
+        }
+
+        if parser.token.kind == TokenKind::Eof {
+            return Some((args, true));
+        }
+    }
+}
+
 /// Rewrite macro name without using pretty-printer if possible.
 fn rewrite_macro_name(
     context: &RewriteContext<'_>,
@@ -1356,10 +1381,23 @@ impl MacroBranch {
             result += " =>";
         }
 
+        match self.rewrite_body(context, shape) {
+            Some(body) => result += &body,
+            // Keep a body we cannot format, e.g., one with repetitions, as written
+            // rather than leaving the formatted matcher out.
+            None if context.config.format_macro_matchers() => {
+                result += " ";
+                result += context.snippet(self.whole_body);
+            }
+            None => return None,
+        }
+
+        Some(result)
+    }
+
+    fn rewrite_body(&self, context: &RewriteContext<'_>, shape: Shape) -> Option<String> {
         if !context.config.format_macro_bodies() {
-            result += " ";
-            result += context.snippet(self.whole_body);
-            return Some(result);
+            return Some(format!(" {}", context.snippet(self.whole_body)));
         }
 
         // The macro body is the most interesting part. It might end up as various
@@ -1375,7 +1413,7 @@ impl MacroBranch {
         let mut config = context.config.clone();
         config.set().hide_parse_errors(true);
 
-        result += " {";
+        let mut result = String::from(" {");
 
         let body_indent = if has_block_body {
             shape.indent
@@ -145,18 +145,25 @@ pub(crate) fn rewrite_match(
 }
 
 fn arm_comma(config: &Config, body: &ast::Expr, is_last: bool) -> &'static str {
+    match body.kind {
+        ast::ExprKind::Block(ref block, _)
+            if matches!(block.rules, ast::BlockCheckMode::Default) =>
+        {
+            block_arm_comma(config, is_last)
+        }
+        _ if is_last && config.trailing_comma() == SeparatorTactic::Never => "",
+        _ => ",",
+    }
+}
+
+/// The comma to put after an arm whose body is (or is wrapped into) a block.
+fn block_arm_comma(config: &Config, is_last: bool) -> &'static str {
     if is_last && config.trailing_comma() == SeparatorTactic::Never {
         ""
     } else if config.match_block_trailing_comma() {
         ","
-    } else if let ast::ExprKind::Block(ref block, _) = body.kind {
-        if let ast::BlockCheckMode::Default = block.rules {
-            ""
-        } else {
-            ","
-        }
     } else {
-        ","
+        ""
     }
 }
 
@@ -309,6 +316,7 @@ fn block_can_be_flattened<'a>(
         ast::ExprKind::Block(ref block, _)
             if !is_unsafe_block(block)
                 && !context.inside_macro()
+                && context.config.unwrap_match_arm_blocks()
                 && is_simple_block(context, block, Some(&expr.attrs))
                 // Don't flatten a block containing a macro invocation,
                 // since it may expand to a statement
@@ -388,8 +396,7 @@ fn rewrite_match_body(
             _ => " ",
         };
         let (body, comma) = match body_str {
-            "()" if context.config.match_block_trailing_comma() => ("{}", ","),
-            "()" => ("{}", ""),
+            "()" => ("{}", block_arm_comma(context.config, is_last)),
             _ => (body_str, comma),
         };
         Some(format!("{} =>{}{}{}", pats_str, block_sep, body, comma))
@@ -440,11 +447,7 @@ fn rewrite_match_body(
         let indent_str = shape.indent.to_string_with_newline(context.config);
         let (body_prefix, body_suffix) =
             if context.config.match_arm_blocks() && !context.inside_macro() {
-                let comma = if context.config.match_block_trailing_comma() {
-                    ","
-                } else {
-                    ""
-                };
+                let comma = block_arm_comma(context.config, is_last);
                 let semicolon = if semicolon_for_expr(context, body) {
                     ";"
                 } else {
@@ -452,7 +455,7 @@ fn rewrite_match_body(
                 };
                 ("{", format!("{}{}}}{}", semicolon, indent_str, comma))
             } else {
-                ("", String::from(","))
+                ("", String::from(comma))
             };
 
         let block_sep = match context.config.control_brace_style() {
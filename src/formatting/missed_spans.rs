@@ -1,3 +1,5 @@
+use std::cmp::max;
+
 use rustc_span::{BytePos, Span};
 
 use crate::config::{file_lines::FileLines, FileName};
@@ -52,6 +54,7 @@ impl<'a> FmtVisitor<'a> {
         }
         self.format_missing_inner(end, |this, last_snippet, _| this.push_str(last_snippet));
         self.normalize_vertical_spaces = false;
+        self.blank_lines_before_item = 0;
     }
 
     pub(crate) fn format_missing_with_indent(&mut self, end: BytePos) {
@@ -66,6 +69,7 @@ impl<'a> FmtVisitor<'a> {
             this.push_str(&indent);
         });
         self.normalize_vertical_spaces = false;
+        self.blank_lines_before_item = 0;
     }
 
     pub(crate) fn format_missing_no_indent(&mut self, end: BytePos) {
@@ -73,6 +77,7 @@ impl<'a> FmtVisitor<'a> {
             this.push_str(last_snippet.trim_end());
         });
         self.normalize_vertical_spaces = false;
+        self.blank_lines_before_item = 0;
     }
 
     fn format_missing_inner<F: Fn(&mut FmtVisitor<'_>, &str, &str)>(
@@ -118,8 +123,10 @@ impl<'a> FmtVisitor<'a> {
 
     fn normalize_newline_count(&self, mut newline_count: usize) -> usize {
         let offset = self.buffer.chars().rev().take_while(|c| *c == '\n').count();
-        let newline_upper_bound = self.config.blank_lines_upper_bound() + 1;
-        let newline_lower_bound = self.config.blank_lines_lower_bound() + 1;
+        let newline_upper_bound =
+            max(self.config.blank_lines_upper_bound(), self.blank_lines_before_item) + 1;
+        let newline_lower_bound =
+            max(self.config.blank_lines_lower_bound(), self.blank_lines_before_item) + 1;
 
         if newline_count + offset > newline_upper_bound {
             if offset >= newline_upper_bound {
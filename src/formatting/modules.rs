@@ -1,18 +1,20 @@
 use std::borrow::Cow;
-use std::collections::BTreeMap;
+use std::collections::{btree_map, BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::io;
 use std::path::{Path, PathBuf};
 
 use rustc_ast::ast;
-use rustc_ast::visit::Visitor;
+use rustc_ast::visit::{self, Visitor};
 use rustc_ast::AstLike;
-use rustc_span::symbol::{self, sym, Symbol};
-use rustc_span::Span;
+use rustc_ast_pretty::pprust;
+use rustc_span::symbol::{self, sym};
+use rustc_span::{BytePos, Span};
 use thiserror::Error;
 
-use crate::config::FileName;
+use crate::config::{ActiveCfgs, Config, FileName, ModMacros, ModulePathRemaps};
 use crate::formatting::{
-    attr::MetaVisitor,
-    items::is_mod_decl,
+    attr::{attr_str_value, mac_str_arg, MetaVisitor},
     syntux::parser::{
         Directory, DirectoryOwnership, ModError, ModulePathSuccess, Parser, ParserError,
     },
@@ -22,21 +24,89 @@ use crate::formatting::{
 
 mod visitor;
 
-pub(crate) type FileModMap<'ast> = BTreeMap<FileName, Module<'ast>>;
+/// Maps each file to its module.
+#[derive(Default)]
+pub(crate) struct FileModMap<'ast> {
+    modules: BTreeMap<FileName, Module<'ast>>,
+    /// The directory ownership each file was found under, which is needed to resolve the
+    /// `mod` declarations of the file again.
+    ownerships: BTreeMap<FileName, DirectoryOwnership>,
+    /// The inner attributes of the modules, keyed by the span of their `mod` item. When several
+    /// files share a `mod` item, the first file in order wins.
+    inner_attrs: HashMap<Span, Vec<ast::Attribute>>,
+    /// The spans of the `#[path]` attributes that are ignored because an earlier one on the
+    /// same module wins.
+    ignored_path_attrs: Vec<Span>,
+    /// The names of the modules, or of the `include!` calls, that were not resolved, and why.
+    skipped_mods: Vec<(String, SkipReason)>,
+}
+
+impl<'ast> FileModMap<'ast> {
+    fn insert(&mut self, file: FileName, ownership: DirectoryOwnership, mut module: Module<'ast>) {
+        if module.source_file.is_none() {
+            module.source_file = Some(file.clone());
+        }
+        self.ownerships.insert(file.clone(), ownership);
+        self.modules.insert(file, module);
+    }
+
+    fn insert_if_absent(
+        &mut self,
+        file: FileName,
+        ownership: DirectoryOwnership,
+        module: Module<'ast>,
+    ) {
+        if !self.modules.contains_key(&file) {
+            self.insert(file, ownership, module);
+        }
+    }
+
+    /// Returns the directory ownership that `file` was found under.
+    pub(crate) fn directory_ownership(&self, file: &FileName) -> Option<DirectoryOwnership> {
+        self.ownerships.get(file).copied()
+    }
+
+    /// Returns the spans of the `#[path]` attributes that are ignored because an earlier one on
+    /// the same module wins. They do not prevent the modules from being resolved.
+    pub(crate) fn ignored_path_attrs(&self) -> &[Span] {
+        &self.ignored_path_attrs
+    }
+
+    /// Returns the names of the modules, or of the `include!` calls, that were not resolved, and
+    /// why. Such modules are not an error, e.g., their files may be generated by a build script.
+    pub(crate) fn skipped_mods(&self) -> &[(String, SkipReason)] {
+        &self.skipped_mods
+    }
+
+    /// Builds the index used by `get_mod_inner_attrs`, once every module has been inserted.
+    fn index_inner_attrs(&mut self) {
+        for module in self.modules.values() {
+            if let Some(ref ast_item) = module.ast_item {
+                self.inner_attrs
+                    .entry(ast_item.span)
+                    .or_insert_with(|| module.inner_attr.clone());
+            }
+        }
+    }
+}
+
+impl<'a, 'ast> IntoIterator for &'a FileModMap<'ast> {
+    type Item = (&'a FileName, &'a Module<'ast>);
+    type IntoIter = btree_map::Iter<'a, FileName, Module<'ast>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.modules.iter()
+    }
+}
 
 pub(crate) fn get_mod_inner_attrs<'a>(
     item: &'a ast::Item,
     file_mod_map: &'a FileModMap<'_>,
 ) -> Option<&'a [ast::Attribute]> {
-    file_mod_map.values().find_map(|m| {
-        m.ast_item.as_ref().and_then(|ast_item| {
-            if ast_item.span == item.span {
-                Some(m.inner_attr.as_slice())
-            } else {
-                None
-            }
-        })
-    })
+    file_mod_map
+        .inner_attrs
+        .get(&item.span)
+        .map(|attrs| attrs.as_slice())
 }
 
 /// Represents module with its inner attributes.
@@ -47,7 +117,14 @@ pub(crate) struct Module<'a> {
     attrs: Cow<'a, Vec<ast::Attribute>>,
     ast_item: Option<Cow<'a, ast::Item>>,
     inner_attr: Vec<ast::Attribute>,
+    /// Where the inner attributes end in the source, recorded before `visit_attrs` can change
+    /// them.
+    inner_attrs_end: Option<BytePos>,
     pub(crate) span: Span,
+    /// The file the items of the module were read from, once it is known.
+    source_file: Option<FileName>,
+    /// Whether `source_file` starts with a byte order mark, which is not part of the source map.
+    has_byte_order_mark: bool,
 }
 
 impl<'a> Module<'a> {
@@ -79,24 +156,66 @@ impl<'a> Module<'a> {
         mod_items: Cow<'a, Vec<rustc_ast::ptr::P<ast::Item>>>,
         mod_attrs: Cow<'a, Vec<ast::Attribute>>,
     ) -> Self {
-        let inner_attr = mod_attrs
+        let inner_attr: Vec<_> = mod_attrs
             .iter()
             .filter(|attr| attr.style == ast::AttrStyle::Inner)
             .cloned()
             .collect();
+        let inner_attrs_end = inner_attr.last().map(|attr| attr.span.hi());
         Module {
             ast_mod_kind,
             ast_item,
             items: mod_items,
             attrs: mod_attrs,
             inner_attr,
+            inner_attrs_end,
             span: mod_span,
+            source_file: None,
+            has_byte_order_mark: false,
         }
     }
 
+    /// Returns `true` for the module of the crate root.
+    pub(crate) fn is_crate_root(&self) -> bool {
+        self.ast_item.is_none()
+    }
+
     pub(crate) fn outside_ast_mod_span(&self) -> Option<Span> {
         self.ast_item.as_ref().map(|item| item.span)
     }
+
+    /// Returns the position right after the last inner attribute of the module, if any.
+    pub(crate) fn inner_attrs_end(&self) -> Option<BytePos> {
+        self.inner_attrs_end
+    }
+
+    /// Returns `true` if the module is declared as `mod foo;` and its items live in another
+    /// file, rather than in an inline `mod foo { .. }`.
+    pub(crate) fn is_external(&self) -> bool {
+        matches!(
+            self.ast_mod_kind.as_deref(),
+            Some(ast::ModKind::Unloaded) | Some(ast::ModKind::Loaded(_, ast::Inline::No, _))
+        )
+    }
+
+    /// Returns the file the items of the module were read from. This is only known for the
+    /// modules in a `FileModMap`, where it differs from the key of the module for the files
+    /// found through `module_path_remaps`.
+    pub(crate) fn source_file(&self) -> Option<&FileName> {
+        self.source_file.as_ref()
+    }
+
+    /// Returns `true` if the file the items of the module were read from starts with a byte order
+    /// mark.
+    pub(crate) fn has_byte_order_mark(&self) -> bool {
+        self.has_byte_order_mark
+    }
+
+    fn read_from(mut self, file: &Path, has_byte_order_mark: bool) -> Self {
+        self.source_file = Some(FileName::Real(file.to_path_buf()));
+        self.has_byte_order_mark = has_byte_order_mark;
+        self
+    }
 }
 
 impl<'a> AstLike for Module<'a> {
@@ -112,12 +231,136 @@ impl<'a> AstLike for Module<'a> {
     }
 }
 
+/// The files of a crate and the `mod` declarations that link them together, as found by
+/// module resolution. Inline modules are part of the file that contains them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleTree {
+    children: BTreeMap<FileName, BTreeSet<FileName>>,
+}
+
+impl ModuleTree {
+    /// Returns every file of the crate, in order.
+    pub fn files(&self) -> impl Iterator<Item = &FileName> {
+        self.children.keys()
+    }
+
+    /// Returns the files reached through the `mod` declarations of `file`, in order.
+    pub fn children<'a>(&'a self, file: &FileName) -> impl Iterator<Item = &'a FileName> {
+        self.children.get(file).into_iter().flatten()
+    }
+
+    fn add_file(&mut self, file: FileName) {
+        self.children.entry(file).or_default();
+    }
+
+    fn add_child(&mut self, parent: &FileName, child: FileName) {
+        self.add_file(child.clone());
+        self.children
+            .entry(parent.clone())
+            .or_default()
+            .insert(child);
+    }
+}
+
+/// The operations on a parse session that module resolution relies on. `ParseSess` implements
+/// them with a real parser, but module discovery can be backed by something lighter, see
+/// `ModResolver::with_session`. The file system queries read the disk unless they are overridden,
+/// e.g., by a session that keeps its files in memory.
+pub trait ModuleSession {
+    fn span_to_filename(&self, span: Span) -> FileName;
+
+    /// Returns `true` if the file at `path` has already been parsed in this session.
+    fn is_file_parsed(&self, path: &Path) -> bool;
+
+    /// Finds the file of `mod id;` in `dir_path`, without `#[path]` attributes.
+    fn default_submod_path(
+        &self,
+        id: symbol::Ident,
+        relative: Option<symbol::Ident>,
+        dir_path: &Path,
+    ) -> Result<ModulePathSuccess, ModError<'_>>;
+
+    /// Parses the file at `path` as the body of a module. The returned flag tells whether the
+    /// file starts with a byte order mark.
+    fn parse_file_as_module(
+        &self,
+        path: &Path,
+        span: Option<Span>,
+    ) -> Result<
+        (Vec<ast::Attribute>, Vec<rustc_ast::ptr::P<ast::Item>>, Span, bool),
+        ParserError,
+    >;
+
+    /// Returns `true` if there is a file or a directory at `path`.
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    /// Returns `true` if there is a file at `path`.
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    /// Returns the absolute form of `path` with all symlinks resolved.
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        path.canonicalize()
+    }
+}
+
+impl ModuleSession for ParseSess {
+    fn span_to_filename(&self, span: Span) -> FileName {
+        ParseSess::span_to_filename(self, span)
+    }
+
+    fn is_file_parsed(&self, path: &Path) -> bool {
+        ParseSess::is_file_parsed(self, path)
+    }
+
+    fn default_submod_path(
+        &self,
+        id: symbol::Ident,
+        relative: Option<symbol::Ident>,
+        dir_path: &Path,
+    ) -> Result<ModulePathSuccess, ModError<'_>> {
+        ParseSess::default_submod_path(self, id, relative, dir_path)
+    }
+
+    fn parse_file_as_module(
+        &self,
+        path: &Path,
+        span: Option<Span>,
+    ) -> Result<
+        (Vec<ast::Attribute>, Vec<rustc_ast::ptr::P<ast::Item>>, Span, bool),
+        ParserError,
+    > {
+        Parser::parse_file_as_module(self, path, span)
+    }
+}
+
 /// Maps each module to the corresponding file.
-pub(crate) struct ModResolver<'ast, 'sess> {
-    parse_sess: &'sess ParseSess,
+pub struct ModResolver<'ast, 'sess> {
+    parse_sess: &'sess dyn ModuleSession,
+    /// Parses the bodies of `cfg_if!` calls and of the macros listed in `mod_macros`. Without
+    /// it, the modules declared in such calls are not resolved.
+    macro_sess: Option<&'sess ParseSess>,
+    mod_macros: ModMacros,
+    include_macro_mods: bool,
+    active_cfgs: ActiveCfgs,
+    path_remaps: ModulePathRemaps,
     directory: Directory,
     file_map: FileModMap<'ast>,
     recursive: bool,
+    /// The file that contains the module being visited.
+    current_file: FileName,
+    /// The files from the crate root down to `current_file`, used to detect cycles.
+    file_chain: Vec<PathBuf>,
+    /// The number of modules being visited, from the crate root down to the current one.
+    depth: usize,
+    max_depth: usize,
+    /// The canonical paths of the resolved files, mapped to the path each file was first
+    /// reached by. Symlinks can make the same file reachable by several paths.
+    resolved_files: HashMap<PathBuf, PathBuf>,
+    module_tree: ModuleTree,
 }
 
 /// Represents errors while trying to resolve modules.
@@ -133,25 +376,103 @@ pub(crate) enum ModuleResolutionErrorKind {
     /// Find a file that cannot be parsed.
     #[error("cannot parse {file}")]
     ParseError { file: PathBuf },
-    /// File cannot be found.
-    #[error("{file} does not exist")]
-    NotFound { file: PathBuf },
+    /// File cannot be found at any of the paths where it was looked for.
+    #[error("file not found, tried:{}", display_candidates(.candidates))]
+    NotFound { candidates: Vec<PathBuf> },
+    /// Both `foo.rs` and `foo/mod.rs` exist for `mod foo;`.
+    #[error("file for module found at both {}", display_paths(.paths))]
+    MultipleCandidates { paths: Vec<PathBuf> },
+    /// A `#[path]` attribute refers to a file that is already being visited.
+    #[error("module cycle: {} -> {}", display_chain(.chain), .path.display())]
+    Cycle { path: PathBuf, chain: Vec<PathBuf> },
+    /// Modules are nested deeper than `max_module_depth`.
+    #[error("modules are nested more than {limit} levels deep (see `max_module_depth` option)")]
+    DepthExceeded { limit: usize },
+}
+
+/// Why a module was not resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SkipReason {
+    /// The module has a `#[rustfmt::skip]` attribute.
+    SkipAttr,
+    /// A `#[cfg(..)]` attribute of the module is false for the active cfg options.
+    InactiveCfg,
+    /// The `#[path]` attribute of the module, or the argument of the `include!` call, refers to
+    /// the given environment variable, which is not set.
+    MissingEnvVar(String),
+    /// The file pulled in by the `include!` call cannot be read.
+    UnreadableInclude(PathBuf),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::SkipAttr => write!(f, "it has a `#[rustfmt::skip]` attribute"),
+            SkipReason::InactiveCfg => write!(f, "its `#[cfg(..)]` is not active"),
+            SkipReason::MissingEnvVar(var) => {
+                write!(f, "its path refers to the unset env variable `{}`", var)
+            }
+            SkipReason::UnreadableInclude(path) => {
+                write!(f, "the included file {} cannot be read", path.display())
+            }
+        }
+    }
+}
+
+/// What `peek_sub_mod` found for a module.
+enum PeekedSubMod<'ast> {
+    Resolved(SubModKind<'ast>),
+    Skipped { reason: SkipReason },
+    /// The file of the module was already resolved, e.g., through another `mod` item.
+    AlreadyParsed { path: PathBuf },
+}
+
+impl<'ast> From<SubModKind<'ast>> for PeekedSubMod<'ast> {
+    fn from(sub_mod_kind: SubModKind<'ast>) -> Self {
+        PeekedSubMod::Resolved(sub_mod_kind)
+    }
 }
 
 #[derive(Clone)]
 enum SubModKind<'ast> {
     /// `mod foo;`
     External(PathBuf, DirectoryOwnership, Module<'ast>),
-    /// `mod foo;` with multiple sources.
+    /// `mod foo;` with multiple sources, like several `#[cfg_attr(.., path = "..")]`. Created
+    /// with `SubModKind::multi_external`.
     MultiExternal(Vec<(PathBuf, DirectoryOwnership, Module<'ast>)>),
     /// `mod foo {}`
     Internal(Cow<'ast, ast::Item>),
 }
 
+impl<'ast> SubModKind<'ast> {
+    /// Sorts the sources by path, so that they are always visited in the same order. The sort is
+    /// stable: when a path is listed more than once, the first entry stays first, and it is the
+    /// one `insert_sub_mod` keeps.
+    fn multi_external(mut mods: Vec<(PathBuf, DirectoryOwnership, Module<'ast>)>) -> Self {
+        mods.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+        SubModKind::MultiExternal(mods)
+    }
+}
+
 impl<'ast, 'sess> ModResolver<'ast, 'sess> {
     /// Creates a new `ModResolver`.
     pub(crate) fn new(
         parse_sess: &'sess ParseSess,
+        config: &Config,
+        directory_ownership: DirectoryOwnership,
+        recursive: bool,
+    ) -> Self {
+        ModResolver {
+            macro_sess: Some(parse_sess),
+            ..ModResolver::with_session(parse_sess, config, directory_ownership, recursive)
+        }
+    }
+
+    /// Creates a new `ModResolver` backed by any `ModuleSession`. Such a resolver cannot parse
+    /// macro calls, so the modules declared in `cfg_if!` and `mod_macros` calls are not resolved.
+    pub fn with_session(
+        parse_sess: &'sess dyn ModuleSession,
+        config: &Config,
         directory_ownership: DirectoryOwnership,
         recursive: bool,
     ) -> Self {
@@ -160,27 +481,57 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                 path: PathBuf::new(),
                 ownership: directory_ownership,
             },
-            file_map: BTreeMap::new(),
+            file_map: FileModMap::default(),
             parse_sess,
+            macro_sess: None,
+            mod_macros: config.mod_macros(),
+            include_macro_mods: config.include_macro_mods(),
+            active_cfgs: config.active_cfgs(),
+            path_remaps: config.module_path_remaps(),
             recursive,
+            current_file: FileName::Stdin,
+            file_chain: vec![],
+            depth: 0,
+            max_depth: config.max_module_depth(),
+            resolved_files: HashMap::new(),
+            module_tree: ModuleTree::default(),
         }
     }
 
     /// Creates a map that maps a file name to the module in AST.
+    ///
+    /// In recursive mode, every module that cannot be resolved is reported instead of only
+    /// the first one.
     pub(crate) fn visit_crate(
+        self,
+        krate: &'ast ast::Crate,
+    ) -> Result<FileModMap<'ast>, Vec<ModuleResolutionError>> {
+        self.visit_crate_with_tree(krate).map(|(file_map, _)| file_map)
+    }
+
+    /// Like `visit_crate`, but also returns which files are reached from which.
+    pub(crate) fn visit_crate_with_tree(
         mut self,
         krate: &'ast ast::Crate,
-    ) -> Result<FileModMap<'ast>, ModuleResolutionError> {
+    ) -> Result<(FileModMap<'ast>, ModuleTree), Vec<ModuleResolutionError>> {
         let root_filename = self.parse_sess.span_to_filename(krate.span);
+        let root_ownership = self.directory.ownership;
         self.directory.path = match root_filename {
             FileName::Real(ref p) => p.parent().unwrap_or_else(|| Path::new("")).to_path_buf(),
             _ => PathBuf::new(),
         };
+        self.current_file = root_filename.clone();
+        self.module_tree.add_file(root_filename.clone());
+        if let FileName::Real(ref p) = root_filename {
+            self.record_resolved_file(p);
+            self.file_chain.push(p.clone());
+        }
 
         self.visit_mod_from_ast(&krate.items)?;
 
         self.file_map.insert(
             root_filename,
+            root_ownership,
             Module::new(
                 krate.span,
                 None,
@@ -189,61 +540,153 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                 Cow::Borrowed(&krate.attrs),
             ),
         );
-        Ok(self.file_map)
+        self.file_map.index_inner_attrs();
+        Ok((self.file_map, self.module_tree))
     }
 
-    /// Visit `cfg_if` macro and look for module declarations.
-    fn visit_cfg_if(&mut self, item: Cow<'ast, ast::Item>) -> Result<(), ModuleResolutionError> {
-        let mut visitor = visitor::CfgIfVisitor::new(self.parse_sess);
+    /// Returns the paths of the files that the `mod` declarations of `krate` refer to, without
+    /// parsing them. Only the crate root and the inline modules and blocks in it are walked, so
+    /// the sub-modules of the returned files are not included.
+    ///
+    /// Every module that cannot be resolved is reported instead of only the first one.
+    pub(crate) fn find_sub_mod_paths(
+        mut self,
+        krate: &ast::Crate,
+    ) -> Result<Vec<PathBuf>, Vec<ModuleResolutionError>> {
+        self.directory.path = match self.parse_sess.span_to_filename(krate.span) {
+            FileName::Real(ref p) => p.parent().unwrap_or_else(|| Path::new("")).to_path_buf(),
+            _ => PathBuf::new(),
+        };
+        let mut paths = vec![];
+        let mut errors = vec![];
+        for item in &krate.items {
+            self.find_sub_mod_paths_inner(item, &mut paths, &mut errors);
+        }
+        if errors.is_empty() {
+            Ok(paths)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn find_sub_mod_paths_inner(
+        &mut self,
+        item: &ast::Item,
+        paths: &mut Vec<PathBuf>,
+        errors: &mut Vec<ModuleResolutionError>,
+    ) {
+        let sub_mod_kind = match item.kind {
+            ast::ItemKind::Mod(_, ref sub_mod_kind) => sub_mod_kind,
+            _ => {
+                let mut visitor = visitor::BlockModVisitor::default();
+                visit::walk_item(&mut visitor, item);
+                for mod_item in visitor.mods() {
+                    let old_directory = self.directory.clone();
+                    // Like in rustc, a module declared in a block does not own a directory.
+                    self.directory.ownership = DirectoryOwnership::UnownedViaBlock;
+                    self.find_sub_mod_paths_inner(mod_item, paths, errors);
+                    self.directory = old_directory;
+                }
+                return;
+            }
+        };
+        if contains_skip(&item.attrs) || !self.is_cfg_active(&item.attrs) {
+            return;
+        }
+        if let ast::ModKind::Loaded(ref items, ast::Inline::Yes, _) = sub_mod_kind {
+            let old_directory = self.directory.clone();
+            self.push_inline_mod_directory(item.ident, &item.attrs);
+            for item in items {
+                self.find_sub_mod_paths_inner(item, paths, errors);
+            }
+            self.directory = old_directory;
+        } else {
+            match resolve_external(self.parse_sess, item, &self.directory) {
+                Ok(path) => paths.extend(path),
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    /// Visit `cfg_if` macro, or a macro listed in `mod_macros`, and look for module declarations.
+    fn visit_cfg_if(
+        &mut self,
+        item: Cow<'ast, ast::Item>,
+    ) -> Result<(), Vec<ModuleResolutionError>> {
+        let macro_sess = match self.macro_sess {
+            Some(macro_sess) => macro_sess,
+            None => return Ok(()),
+        };
+        let mut errors = vec![];
+        let mut visitor = visitor::CfgIfVisitor::new(macro_sess, &self.mod_macros);
         visitor.visit_item(&item);
         for module_item in visitor.mods() {
             if let ast::ItemKind::Mod(_, ref sub_mod_kind) = module_item.item.kind {
-                self.visit_sub_mod(Module::new(
+                let result = self.visit_sub_mod(Module::new(
                     module_item.item.span,
                     Some(Cow::Owned(sub_mod_kind.clone())),
                     Some(Cow::Owned(module_item.item)),
                     Cow::Owned(vec![]),
                     Cow::Owned(vec![]),
-                ))?;
+                ));
+                collect_errors(&mut errors, result);
             }
         }
-        Ok(())
+        errors_to_result(errors)
     }
 
     /// Visit modules defined inside macro calls.
     fn visit_mod_outside_ast(
         &mut self,
         items: Vec<rustc_ast::ptr::P<ast::Item>>,
-    ) -> Result<(), ModuleResolutionError> {
+    ) -> Result<(), Vec<ModuleResolutionError>> {
+        let mut errors = vec![];
         for item in items {
-            if is_cfg_if(&item) {
-                self.visit_cfg_if(Cow::Owned(item.into_inner()))?;
+            if self.is_mod_macro_call(&item) {
+                let result = self.visit_cfg_if(Cow::Owned(item.into_inner()));
+                collect_errors(&mut errors, result);
+                continue;
+            }
+            if self.is_include_macro_call(&item) {
+                let result = self.visit_include_macro(&item);
+                collect_errors(&mut errors, result);
                 continue;
             }
 
             if let ast::ItemKind::Mod(_, ref sub_mod_kind) = item.kind {
-                self.visit_sub_mod(Module::new(
+                let result = self.visit_sub_mod(Module::new(
                     item.span,
                     Some(Cow::Owned(sub_mod_kind.clone())),
                     Some(Cow::Owned(item.into_inner())),
                     Cow::Owned(vec![]),
                     Cow::Owned(vec![]),
-                ))?;
+                ));
+                collect_errors(&mut errors, result);
+            } else {
+                let result = self.visit_mods_in_blocks(&item);
+                collect_errors(&mut errors, result);
             }
         }
-        Ok(())
+        errors_to_result(errors)
     }
 
     /// Visit modules from AST.
     fn visit_mod_from_ast(
         &mut self,
         items: &'ast Vec<rustc_ast::ptr::P<ast::Item>>,
-    ) -> Result<(), ModuleResolutionError> {
+    ) -> Result<(), Vec<ModuleResolutionError>> {
+        let mut errors = vec![];
         for item in items {
-            if is_cfg_if(item) {
+            if self.is_mod_macro_call(item) {
                 let result = self.visit_cfg_if(Cow::Borrowed(item));
-                if result.is_err() && self.recursive {
-                    return result;
+                if self.recursive {
+                    collect_errors(&mut errors, result);
+                }
+            }
+            if self.is_include_macro_call(item) {
+                let result = self.visit_include_macro(item);
+                if self.recursive {
+                    collect_errors(&mut errors, result);
                 }
             }
 
@@ -255,85 +698,227 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                     Cow::Owned(vec![]),
                     Cow::Borrowed(&item.attrs),
                 ));
-                if result.is_err() && self.recursive {
-                    return result;
+                if self.recursive {
+                    collect_errors(&mut errors, result);
+                }
+            } else {
+                let result = self.visit_mods_in_blocks(item);
+                if self.recursive {
+                    collect_errors(&mut errors, result);
                 }
             }
         }
-        Ok(())
+        errors_to_result(errors)
+    }
+
+    /// Visit modules declared in blocks inside `item`, like in the body of a function.
+    fn visit_mods_in_blocks(&mut self, item: &ast::Item) -> Result<(), Vec<ModuleResolutionError>> {
+        let mut visitor = visitor::BlockModVisitor::default();
+        visit::walk_item(&mut visitor, item);
+        let mut errors = vec![];
+        for mod_item in visitor.mods() {
+            if let ast::ItemKind::Mod(_, ref sub_mod_kind) = mod_item.kind {
+                let old_directory = self.directory.clone();
+                // Like in rustc, a module declared in a block does not own a directory.
+                self.directory.ownership = DirectoryOwnership::UnownedViaBlock;
+                let result = self.visit_sub_mod(Module::new(
+                    mod_item.span,
+                    Some(Cow::Owned(sub_mod_kind.clone())),
+                    Some(Cow::Owned(mod_item.clone())),
+                    Cow::Owned(vec![]),
+                    Cow::Owned(mod_item.attrs.clone()),
+                ));
+                self.directory = old_directory;
+                collect_errors(&mut errors, result);
+            }
+        }
+        errors_to_result(errors)
+    }
+
+    fn is_mod_macro_call(&self, item: &ast::Item) -> bool {
+        match item.kind {
+            ast::ItemKind::MacCall(ref mac) => {
+                visitor::is_cfg_if(&mac.path) || visitor::is_mod_macro(&mac.path, &self.mod_macros)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_include_macro_call(&self, item: &ast::Item) -> bool {
+        match item.kind {
+            ast::ItemKind::MacCall(ref mac) => {
+                self.include_macro_mods && visitor::is_include(&mac.path)
+            }
+            _ => false,
+        }
+    }
+
+    /// Visit the file pulled in by an `include!` call, and look for module declarations in it.
+    /// The modules are resolved as if they were declared in place of the call.
+    fn visit_include_macro(&mut self, item: &ast::Item) -> Result<(), Vec<ModuleResolutionError>> {
+        let mac = match item.kind {
+            ast::ItemKind::MacCall(ref mac) => mac,
+            _ => return Ok(()),
+        };
+        let name = || format!("include!({})", pprust::tts_to_string(&mac.args.inner_tokens()));
+        let path = match mac_str_arg(mac) {
+            Some(Ok(path)) => path,
+            Some(Err(e)) => {
+                // Such files are usually generated by a build script that has not run yet.
+                let reason = SkipReason::MissingEnvVar(e.var().to_owned());
+                self.file_map.skipped_mods.push((name(), reason));
+                return Ok(());
+            }
+            None => return Ok(()),
+        };
+        // Like in rustc, the path is relative to the file that contains the `include!` call.
+        let path = match self.current_file {
+            FileName::Real(ref file) => file.parent().unwrap_or_else(|| Path::new("")).join(path),
+            _ => self.directory.path.join(path),
+        };
+        if self.parse_sess.is_file_parsed(&path) {
+            return Ok(());
+        }
+        match self.parse_sess.parse_file_as_module(&path, Some(item.span)) {
+            Ok((_, items, _, _)) => self.visit_mod_outside_ast(items),
+            Err(..) => {
+                let reason = SkipReason::UnreadableInclude(path);
+                self.file_map.skipped_mods.push((name(), reason));
+                Ok(())
+            }
+        }
     }
 
-    fn visit_sub_mod(&mut self, sub_mod: Module<'ast>) -> Result<(), ModuleResolutionError> {
+    fn visit_sub_mod(&mut self, sub_mod: Module<'ast>) -> Result<(), Vec<ModuleResolutionError>> {
+        if self.depth >= self.max_depth {
+            return Err(vec![ModuleResolutionError {
+                module: sub_mod.name(),
+                kind: ModuleResolutionErrorKind::DepthExceeded {
+                    limit: self.max_depth,
+                },
+            }]);
+        }
         let old_directory = self.directory.clone();
-        let sub_mod_kind = self.peek_sub_mod(&sub_mod)?;
-        if let Some(sub_mod_kind) = sub_mod_kind {
-            self.insert_sub_mod(sub_mod_kind.clone())?;
-            if self.recursive {
-                self.visit_sub_mod_inner(sub_mod, sub_mod_kind)?;
+        let old_file = self.current_file.clone();
+        let old_chain_len = self.file_chain.len();
+        self.file_map
+            .ignored_path_attrs
+            .extend(ignored_path_attrs(sub_mod.outer_attrs()));
+        let peeked = self.peek_sub_mod(&sub_mod).map_err(|e| vec![e])?;
+        self.depth += 1;
+        let mut result = Ok(());
+        match peeked {
+            PeekedSubMod::Resolved(sub_mod_kind) => {
+                self.insert_sub_mod(sub_mod_kind.clone());
+                if self.recursive {
+                    result = self.visit_sub_mod_inner(sub_mod, sub_mod_kind);
+                }
+            }
+            PeekedSubMod::Skipped { reason } => {
+                self.file_map.skipped_mods.push((sub_mod.name(), reason));
+            }
+            PeekedSubMod::AlreadyParsed { path } => {
+                let file = FileName::Real(self.resolved_path(path));
+                self.module_tree.add_child(&self.current_file, file);
             }
         }
+        // Restore the directory even on errors, the caller goes on with the sibling modules.
         self.directory = old_directory;
-        Ok(())
+        self.current_file = old_file;
+        self.file_chain.truncate(old_chain_len);
+        self.depth -= 1;
+        result
     }
 
     /// Inspect the given sub-module which we are about to visit and returns its kind.
     fn peek_sub_mod(
         &self,
         sub_mod: &Module<'ast>,
-    ) -> Result<Option<SubModKind<'ast>>, ModuleResolutionError> {
+    ) -> Result<PeekedSubMod<'ast>, ModuleResolutionError> {
         if contains_skip(&sub_mod.outer_attrs()) {
-            return Ok(None);
+            return Ok(PeekedSubMod::Skipped {
+                reason: SkipReason::SkipAttr,
+            });
+        }
+        if !self.is_cfg_active(sub_mod.outer_attrs()) {
+            return Ok(PeekedSubMod::Skipped {
+                reason: SkipReason::InactiveCfg,
+            });
         }
 
-        if sub_mod
-            .ast_item
-            .as_ref()
-            .map_or(false, |item| is_mod_decl(&item))
-        {
+        if sub_mod.is_external() {
             // mod foo;
             // Look for an extern file.
             self.find_external_module(sub_mod)
         } else {
             // An internal module (`mod foo { /* ... */ }`);
-            Ok(Some(SubModKind::Internal(
+            Ok(PeekedSubMod::Resolved(SubModKind::Internal(
                 sub_mod.ast_item.clone().unwrap(),
             )))
         }
     }
 
-    fn insert_sub_mod(
-        &mut self,
-        sub_mod_kind: SubModKind<'ast>,
-    ) -> Result<(), ModuleResolutionError> {
+    /// Returns `false` if one of the `#[cfg(..)]` attributes is false for the active cfg options.
+    /// Every module is active when no cfg options are given.
+    fn is_cfg_active(&self, attrs: &[ast::Attribute]) -> bool {
+        if self.active_cfgs.is_empty() {
+            return true;
+        }
+        attrs
+            .iter()
+            .filter(|attr| attr.has_name(sym::cfg))
+            .filter_map(|attr| attr.meta_item_list())
+            .all(|list| match list.as_slice() {
+                [predicate] => {
+                    let mut cfg_visitor = visitor::CfgVisitor::new(&self.active_cfgs);
+                    cfg_visitor.visit_nested_meta_item(predicate);
+                    cfg_visitor.is_active()
+                }
+                _ => true,
+            })
+    }
+
+    fn insert_sub_mod(&mut self, sub_mod_kind: SubModKind<'ast>) {
         match sub_mod_kind {
-            SubModKind::External(mod_path, _, sub_mod) => {
-                self.file_map
-                    .entry(FileName::Real(mod_path))
-                    .or_insert(sub_mod);
+            SubModKind::External(mod_path, directory_ownership, sub_mod) => {
+                self.record_resolved_file(&mod_path);
+                self.module_tree
+                    .add_child(&self.current_file, FileName::Real(mod_path.clone()));
+                self.file_map.insert_if_absent(
+                    FileName::Real(mod_path),
+                    directory_ownership,
+                    sub_mod,
+                );
             }
             SubModKind::MultiExternal(mods) => {
-                for (mod_path, _, sub_mod) in mods {
-                    self.file_map
-                        .entry(FileName::Real(mod_path))
-                        .or_insert(sub_mod);
+                for (mod_path, directory_ownership, sub_mod) in mods {
+                    self.record_resolved_file(&mod_path);
+                    self.module_tree
+                        .add_child(&self.current_file, FileName::Real(mod_path.clone()));
+                    self.file_map.insert_if_absent(
+                        FileName::Real(mod_path),
+                        directory_ownership,
+                        sub_mod,
+                    );
                 }
             }
             _ => {}
         }
-        Ok(())
     }
 
     fn visit_sub_mod_inner(
         &mut self,
         sub_mod: Module<'ast>,
         sub_mod_kind: SubModKind<'ast>,
-    ) -> Result<(), ModuleResolutionError> {
+    ) -> Result<(), Vec<ModuleResolutionError>> {
         match sub_mod_kind {
             SubModKind::External(mod_path, directory_ownership, sub_mod) => {
                 let directory = Directory {
                     path: mod_path.parent().unwrap().to_path_buf(),
                     ownership: directory_ownership,
                 };
+                self.file_chain.push(mod_path.clone());
+                self.current_file = FileName::Real(mod_path);
                 self.visit_sub_mod_after_directory_update(sub_mod, Some(directory))
             }
             SubModKind::Internal(ref item) => {
@@ -341,14 +926,22 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                 self.visit_sub_mod_after_directory_update(sub_mod, None)
             }
             SubModKind::MultiExternal(mods) => {
+                let mut errors = vec![];
+                let parent_file = self.current_file.clone();
                 for (mod_path, directory_ownership, sub_mod) in mods {
                     let directory = Directory {
                         path: mod_path.parent().unwrap().to_path_buf(),
                         ownership: directory_ownership,
                     };
-                    self.visit_sub_mod_after_directory_update(sub_mod, Some(directory))?;
+                    self.file_chain.push(mod_path.clone());
+                    self.current_file = FileName::Real(mod_path);
+                    let result =
+                        self.visit_sub_mod_after_directory_update(sub_mod, Some(directory));
+                    collect_errors(&mut errors, result);
+                    self.current_file = parent_file.clone();
+                    self.file_chain.pop();
                 }
-                Ok(())
+                errors_to_result(errors)
             }
         }
     }
@@ -357,14 +950,18 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
         &mut self,
         sub_mod: Module<'ast>,
         directory: Option<Directory>,
-    ) -> Result<(), ModuleResolutionError> {
+    ) -> Result<(), Vec<ModuleResolutionError>> {
         if let Some(directory) = directory {
             self.directory = directory;
         }
         match (sub_mod.ast_mod_kind, sub_mod.items) {
-            (Some(Cow::Borrowed(ast::ModKind::Loaded(items, ast::Inline::No, _))), _) => {
+            (Some(Cow::Borrowed(ast::ModKind::Loaded(items, _, _))), _) => {
                 self.visit_mod_from_ast(&items)
             }
+            // Inline modules found in blocks keep their items in the owned mod kind.
+            (Some(Cow::Owned(ast::ModKind::Loaded(items, ast::Inline::Yes, _))), _) => {
+                self.visit_mod_outside_ast(items)
+            }
             (Some(Cow::Owned(..)), Cow::Owned(items)) => self.visit_mod_outside_ast(items),
             (_, _) => Ok(()),
         }
@@ -374,44 +971,62 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
     fn find_external_module(
         &self,
         sub_mod: &Module<'ast>,
-    ) -> Result<Option<SubModKind<'ast>>, ModuleResolutionError> {
+    ) -> Result<PeekedSubMod<'ast>, ModuleResolutionError> {
         let relative = match self.directory.ownership {
             DirectoryOwnership::Owned { relative } => relative,
             DirectoryOwnership::UnownedViaBlock => None,
         };
-        if let Some(path) =
-            Parser::submod_path_from_attr(sub_mod.outer_attrs(), &self.directory.path)
-        {
-            if self.parse_sess.is_file_parsed(&path) {
-                return Ok(None);
-            }
-            return match Parser::parse_file_as_module(
-                self.parse_sess,
-                &path,
-                sub_mod.outside_ast_mod_span(),
-            ) {
-                Ok((attrs, items, span)) => Ok(Some(SubModKind::External(
-                    path,
-                    DirectoryOwnership::Owned { relative: None },
-                    Module::new(
-                        span,
-                        Some(Cow::Owned(ast::ModKind::Unloaded)),
-                        sub_mod.ast_item.clone(),
-                        Cow::Owned(items),
-                        Cow::Owned(attrs),
-                    ),
-                ))),
+        let attr_path = Parser::submod_path_from_attr(sub_mod.outer_attrs(), &self.directory.path);
+        if let Some(Err(ref e)) = attr_path {
+            // Such modules are usually generated by a build script that has not run yet.
+            return Ok(PeekedSubMod::Skipped {
+                reason: SkipReason::MissingEnvVar(e.var().to_owned()),
+            });
+        }
+        if let Some(Ok(path)) = attr_path {
+            self.check_cycle(sub_mod, &path)?;
+            let file = self.remapped_path(&path);
+            if self.parse_sess.is_file_parsed(&file) || self.is_resolved_under_other_path(&path) {
+                return Ok(PeekedSubMod::AlreadyParsed { path });
+            }
+            return match self
+                .parse_sess
+                .parse_file_as_module(&file, sub_mod.outside_ast_mod_span())
+            {
+                Ok((attrs, items, span, has_byte_order_mark)) => {
+                    Ok(PeekedSubMod::Resolved(SubModKind::External(
+                        path,
+                        DirectoryOwnership::Owned { relative: None },
+                        Module::new(
+                            span,
+                            Some(Cow::Owned(ast::ModKind::Unloaded)),
+                            sub_mod.ast_item.clone(),
+                            Cow::Owned(items),
+                            Cow::Owned(attrs),
+                        )
+                        .read_from(&file, has_byte_order_mark),
+                    )))
+                }
                 Err(ParserError::ParseError) => Err(ModuleResolutionError {
                     module: sub_mod.name(),
                     kind: ModuleResolutionErrorKind::ParseError { file: path },
                 }),
                 Err(..) => Err(ModuleResolutionError {
                     module: sub_mod.name(),
-                    kind: ModuleResolutionErrorKind::NotFound { file: path },
+                    kind: ModuleResolutionErrorKind::NotFound {
+                        candidates: vec![path],
+                    },
                 }),
             };
         }
 
+        if let Some(paths) = self.find_multiple_candidates(sub_mod.ident(), relative) {
+            return Err(ModuleResolutionError {
+                module: sub_mod.name(),
+                kind: ModuleResolutionErrorKind::MultipleCandidates { paths },
+            });
+        }
+
         // Look for nested path, like `#[cfg_attr(feature = "foo", path = "bar.rs")]`.
         let mut mods_outside_ast = self.find_mods_outside_of_ast(sub_mod);
 
@@ -424,27 +1039,35 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                 dir_ownership,
                 ..
             }) => {
+                self.check_cycle(sub_mod, &file_path)?;
+                let file = self.remapped_path(&file_path);
                 let outside_mods_empty = mods_outside_ast.is_empty();
                 let should_insert = !mods_outside_ast
                     .iter()
                     .any(|(outside_path, _, _)| outside_path == &file_path);
-                if self.parse_sess.is_file_parsed(&file_path) {
+                if self.is_resolved_under_other_path(&file_path) {
+                    return if outside_mods_empty {
+                        Ok(PeekedSubMod::AlreadyParsed { path: file_path })
+                    } else {
+                        Ok(SubModKind::multi_external(mods_outside_ast).into())
+                    };
+                }
+                if self.parse_sess.is_file_parsed(&file) {
                     if outside_mods_empty {
-                        return Ok(None);
+                        return Ok(PeekedSubMod::AlreadyParsed { path: file_path });
                     } else {
                         if should_insert {
                             mods_outside_ast.push((file_path, dir_ownership, sub_mod.clone()));
                         }
-                        return Ok(Some(SubModKind::MultiExternal(mods_outside_ast)));
+                        return Ok(SubModKind::multi_external(mods_outside_ast).into());
                     }
                 }
-                match Parser::parse_file_as_module(
-                    self.parse_sess,
-                    &file_path,
-                    sub_mod.outside_ast_mod_span(),
-                ) {
-                    Ok((attrs, items, span)) if outside_mods_empty => {
-                        Ok(Some(SubModKind::External(
+                match self
+                    .parse_sess
+                    .parse_file_as_module(&file, sub_mod.outside_ast_mod_span())
+                {
+                    Ok((attrs, items, span, has_byte_order_mark)) if outside_mods_empty => {
+                        Ok(PeekedSubMod::Resolved(SubModKind::External(
                             file_path,
                             dir_ownership,
                             Module::new(
@@ -453,10 +1076,11 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                                 sub_mod.ast_item.clone(),
                                 Cow::Owned(items),
                                 Cow::Owned(attrs),
-                            ),
+                            )
+                            .read_from(&file, has_byte_order_mark),
                         )))
                     }
-                    Ok((attrs, items, span)) => {
+                    Ok((attrs, items, span, has_byte_order_mark)) => {
                         mods_outside_ast.push((
                             file_path.clone(),
                             dir_ownership,
@@ -466,12 +1090,13 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                                 sub_mod.ast_item.clone(),
                                 Cow::Owned(items),
                                 Cow::Owned(attrs),
-                            ),
+                            )
+                            .read_from(&file, has_byte_order_mark),
                         ));
                         if should_insert {
                             mods_outside_ast.push((file_path, dir_ownership, sub_mod.clone()));
                         }
-                        Ok(Some(SubModKind::MultiExternal(mods_outside_ast)))
+                        Ok(SubModKind::multi_external(mods_outside_ast).into())
                     }
                     Err(ParserError::ParseError) => Err(ModuleResolutionError {
                         module: sub_mod.name(),
@@ -479,13 +1104,15 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                     }),
                     Err(..) if outside_mods_empty => Err(ModuleResolutionError {
                         module: sub_mod.name(),
-                        kind: ModuleResolutionErrorKind::NotFound { file: file_path },
+                        kind: ModuleResolutionErrorKind::NotFound {
+                            candidates: vec![file_path],
+                        },
                     }),
                     Err(..) => {
                         if should_insert {
                             mods_outside_ast.push((file_path, dir_ownership, sub_mod.clone()));
                         }
-                        Ok(Some(SubModKind::MultiExternal(mods_outside_ast)))
+                        Ok(SubModKind::multi_external(mods_outside_ast).into())
                     }
                 }
             }
@@ -493,20 +1120,175 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                 if let ModError::ParserError(mut e) = mod_err {
                     e.cancel();
                 }
-                Ok(Some(SubModKind::MultiExternal(mods_outside_ast)))
+                Ok(SubModKind::multi_external(mods_outside_ast).into())
+            }
+            Err(_) => {
+                let mut candidates = self.default_candidate_paths(sub_mod.ident(), relative);
+                if let Some(peeked) = self.find_remapped_module(sub_mod, &candidates)? {
+                    return Ok(peeked);
+                }
+                candidates.extend(self.outside_ast_candidate_paths(sub_mod));
+                Err(ModuleResolutionError {
+                    module: sub_mod.name(),
+                    kind: ModuleResolutionErrorKind::NotFound { candidates },
+                })
+            }
+        }
+    }
+
+    /// Returns where the file at `path` is read from, after applying `module_path_remaps`.
+    fn remapped_path(&self, path: &Path) -> PathBuf {
+        self.path_remaps
+            .remap(path)
+            .unwrap_or_else(|| path.to_path_buf())
+    }
+
+    /// Looks for the file of `sub_mod` at the default candidate paths rewritten by
+    /// `module_path_remaps`, as the file is not where the module is declared.
+    fn find_remapped_module(
+        &self,
+        sub_mod: &Module<'ast>,
+        candidates: &[PathBuf],
+    ) -> Result<Option<PeekedSubMod<'ast>>, ModuleResolutionError> {
+        let found = candidates.iter().enumerate().find_map(|(i, path)| {
+            let file = self.path_remaps.remap(path)?;
+            if self.parse_sess.is_file(&file) {
+                Some((i, path, file))
+            } else {
+                None
             }
-            Err(_) => Err(ModuleResolutionError {
+        });
+        let (i, path, file) = match found {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        self.check_cycle(sub_mod, path)?;
+        if self.parse_sess.is_file_parsed(&file) || self.is_resolved_under_other_path(path) {
+            return Ok(Some(PeekedSubMod::AlreadyParsed { path: path.clone() }));
+        }
+        // The sub-modules of `foo.rs` are in `foo/`, like those of `foo/mod.rs`.
+        let relative = if i == 0 { Some(sub_mod.ident()) } else { None };
+        match self
+            .parse_sess
+            .parse_file_as_module(&file, sub_mod.outside_ast_mod_span())
+        {
+            Ok((attrs, items, span, has_byte_order_mark)) => {
+                Ok(Some(PeekedSubMod::Resolved(SubModKind::External(
+                    path.clone(),
+                    DirectoryOwnership::Owned { relative },
+                    Module::new(
+                        span,
+                        Some(Cow::Owned(ast::ModKind::Unloaded)),
+                        sub_mod.ast_item.clone(),
+                        Cow::Owned(items),
+                        Cow::Owned(attrs),
+                    )
+                    .read_from(&file, has_byte_order_mark),
+                ))))
+            }
+            Err(ParserError::ParseError) => Err(ModuleResolutionError {
+                module: sub_mod.name(),
+                kind: ModuleResolutionErrorKind::ParseError { file: path.clone() },
+            }),
+            Err(..) => Ok(None),
+        }
+    }
+
+    /// Returns an error if `path` is one of the files being visited, as visiting it again
+    /// would never end. Files reached again through a different chain are fine.
+    fn check_cycle(
+        &self,
+        sub_mod: &Module<'ast>,
+        path: &Path,
+    ) -> Result<(), ModuleResolutionError> {
+        match self
+            .file_chain
+            .iter()
+            .position(|p| p == path || is_same_file(self.parse_sess, p, path))
+        {
+            Some(pos) => Err(ModuleResolutionError {
                 module: sub_mod.name(),
-                kind: ModuleResolutionErrorKind::NotFound {
-                    file: self.directory.path.clone(),
+                kind: ModuleResolutionErrorKind::Cycle {
+                    path: path.to_path_buf(),
+                    chain: self.file_chain[pos..].to_vec(),
                 },
             }),
+            None => Ok(()),
+        }
+    }
+
+    /// Records `path` as resolved, so that the file is not resolved again when it is reached
+    /// through another path.
+    fn record_resolved_file(&mut self, path: &Path) {
+        if let Ok(canonical) = self.parse_sess.canonicalize(path) {
+            self.resolved_files
+                .entry(canonical)
+                .or_insert_with(|| path.to_path_buf());
+        }
+    }
+
+    /// Returns the path through which the file at `path` was first resolved.
+    fn resolved_path(&self, path: PathBuf) -> PathBuf {
+        self.parse_sess
+            .canonicalize(&path)
+            .ok()
+            .and_then(|canonical| self.resolved_files.get(&canonical).cloned())
+            .unwrap_or(path)
+    }
+
+    /// Returns `true` if `path` leads to a file that was already resolved through another
+    /// path, e.g., when one of them is a symlink to the other.
+    fn is_resolved_under_other_path(&self, path: &Path) -> bool {
+        self.parse_sess
+            .canonicalize(path)
+            .ok()
+            .and_then(|canonical| self.resolved_files.get(&canonical))
+            .map_or(false, |resolved_path| resolved_path != path)
+    }
+
+    /// Returns the paths of both `foo.rs` and `foo/mod.rs` if they both exist, as rustc
+    /// refuses to pick one of them for `mod foo;`.
+    fn find_multiple_candidates(
+        &self,
+        id: symbol::Ident,
+        relative: Option<symbol::Ident>,
+    ) -> Option<Vec<PathBuf>> {
+        let paths = self.default_candidate_paths(id, relative);
+        if paths.iter().all(|path| self.parse_sess.exists(path)) {
+            Some(paths)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the paths of `foo.rs` and `foo/mod.rs`, where rustc looks for `mod foo;`.
+    fn default_candidate_paths(
+        &self,
+        id: symbol::Ident,
+        relative: Option<symbol::Ident>,
+    ) -> Vec<PathBuf> {
+        candidate_paths(&self.directory.path, id, relative)
+    }
+
+    /// Returns the paths from nested attributes, like
+    /// `#[cfg_attr(feature = "foo", path = "bar.rs")]`.
+    fn outside_ast_candidate_paths(&self, sub_mod: &Module<'ast>) -> Vec<PathBuf> {
+        let mut path_visitor = visitor::PathVisitor::default();
+        for attr in sub_mod.outer_attrs() {
+            if let Some(meta) = attr.meta() {
+                path_visitor.visit_meta_item(&meta)
+            }
         }
+        path_visitor
+            .paths()
+            .into_iter()
+            .map(|path| self.directory.path.join(path))
+            .collect()
     }
 
     fn push_inline_mod_directory(&mut self, id: symbol::Ident, attrs: &[ast::Attribute]) {
         if let Some(path) = find_path_value(attrs) {
-            self.directory.path.push(&*path.as_str());
+            self.directory.path.push(&path);
             self.directory.ownership = DirectoryOwnership::Owned { relative: None };
         } else {
             // We have to push on the current module name in the case of relative
@@ -515,6 +1297,9 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
             //
             // For example, a `mod z { ... }` inside `x/y.rs` should set the current
             // directory path to `/x/y/z`, not `/x/z` with a relative offset of `y`.
+            //
+            // Like in rustc, an inline module inside a block keeps `UnownedViaBlock`, so
+            // the relative offset is not pushed for it nor for the modules nested in it.
             if let DirectoryOwnership::Owned { relative } = &mut self.directory.ownership {
                 if let Some(ident) = relative.take() {
                     // remove the relative offset
@@ -529,19 +1314,11 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
         &self,
         sub_mod: &Module<'ast>,
     ) -> Vec<(PathBuf, DirectoryOwnership, Module<'ast>)> {
-        // Filter nested path, like `#[cfg_attr(feature = "foo", path = "bar.rs")]`.
-        let mut path_visitor = visitor::PathVisitor::default();
-        for attr in sub_mod.outer_attrs() {
-            if let Some(meta) = attr.meta() {
-                path_visitor.visit_meta_item(&meta)
-            }
-        }
-
         let mut result = vec![];
-        for path in path_visitor.paths() {
-            let mut actual_path = self.directory.path.clone();
-            actual_path.push(&path);
-            if !actual_path.exists() {
+        for actual_path in self.outside_ast_candidate_paths(sub_mod) {
+            if !self.parse_sess.exists(&actual_path)
+                || self.is_resolved_under_other_path(&actual_path)
+            {
                 continue;
             }
 
@@ -555,11 +1332,10 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                 continue;
             }
 
-            let (attrs, items, span) = match Parser::parse_file_as_module(
-                self.parse_sess,
-                &actual_path,
-                sub_mod.outside_ast_mod_span(),
-            ) {
+            let (attrs, items, span, has_byte_order_mark) = match self
+                .parse_sess
+                .parse_file_as_module(&actual_path, sub_mod.outside_ast_mod_span())
+            {
                 Ok(m) => m,
                 Err(..) => continue,
             };
@@ -573,16 +1349,81 @@ impl<'ast, 'sess> ModResolver<'ast, 'sess> {
                     sub_mod.ast_item.clone(),
                     Cow::Owned(items),
                     Cow::Owned(attrs),
-                ),
+                )
+                .read_from(&actual_path, has_byte_order_mark),
             ))
         }
         result
     }
 }
 
-fn path_value(attr: &ast::Attribute) -> Option<Symbol> {
+/// Returns the path of the file that the `mod foo;` item refers to when it is declared in `dir`,
+/// without parsing the file. Returns `None` for inline modules, for items that are not modules,
+/// and for `#[path]` attributes that cannot be evaluated.
+pub(crate) fn resolve_external(
+    parse_sess: &dyn ModuleSession,
+    item: &ast::Item,
+    dir: &Directory,
+) -> Result<Option<PathBuf>, ModuleResolutionError> {
+    match item.kind {
+        ast::ItemKind::Mod(_, ast::ModKind::Loaded(_, ast::Inline::Yes, _)) => return Ok(None),
+        ast::ItemKind::Mod(..) => {}
+        _ => return Ok(None),
+    }
+    if let Some(path) = Parser::submod_path_from_attr(&item.attrs, &dir.path) {
+        return Ok(path.ok());
+    }
+    let relative = match dir.ownership {
+        DirectoryOwnership::Owned { relative } => relative,
+        DirectoryOwnership::UnownedViaBlock => None,
+    };
+    let error = |kind| ModuleResolutionError {
+        module: item.ident.to_string(),
+        kind,
+    };
+    let paths = candidate_paths(&dir.path, item.ident, relative);
+    if paths.iter().all(|path| parse_sess.exists(path)) {
+        return Err(error(ModuleResolutionErrorKind::MultipleCandidates { paths }));
+    }
+    match parse_sess.default_submod_path(item.ident, relative, &dir.path) {
+        Ok(ModulePathSuccess { file_path, .. }) => Ok(Some(file_path)),
+        Err(mod_err) => {
+            if let ModError::ParserError(mut e) = mod_err {
+                e.cancel();
+            }
+            Err(error(ModuleResolutionErrorKind::NotFound { candidates: paths }))
+        }
+    }
+}
+
+/// Returns the paths of `foo.rs` and `foo/mod.rs` in `dir_path`, where rustc looks for `mod foo;`.
+fn candidate_paths(
+    dir_path: &Path,
+    id: symbol::Ident,
+    relative: Option<symbol::Ident>,
+) -> Vec<PathBuf> {
+    let mut dir_path = dir_path.to_path_buf();
+    if let Some(ident) = relative {
+        dir_path.push(&*ident.as_str());
+    }
+    let mod_name = id.as_str();
+    vec![
+        dir_path.join(format!("{}.rs", mod_name)),
+        dir_path.join(&*mod_name).join("mod.rs"),
+    ]
+}
+
+/// Returns `true` if both paths lead to the same file once symlinks are followed.
+fn is_same_file(parse_sess: &dyn ModuleSession, a: &Path, b: &Path) -> bool {
+    match (parse_sess.canonicalize(a), parse_sess.canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn path_value(attr: &ast::Attribute) -> Option<String> {
     if attr.has_name(sym::path) {
-        attr.value_str()
+        attr_str_value(attr)?.ok()
     } else {
         None
     }
@@ -591,20 +1432,290 @@ fn path_value(attr: &ast::Attribute) -> Option<Symbol> {
 // N.B., even when there are multiple `#[path = ...]` attributes, we just need to
 // examine the first one, since rustc ignores the second and the subsequent ones
 // as unused attributes.
-fn find_path_value(attrs: &[ast::Attribute]) -> Option<Symbol> {
+fn find_path_value(attrs: &[ast::Attribute]) -> Option<String> {
     attrs.iter().flat_map(path_value).next()
 }
 
-fn is_cfg_if(item: &ast::Item) -> bool {
-    match item.kind {
-        ast::ItemKind::MacCall(ref mac) => {
-            if let Some(last_segment) = mac.path.segments.last() {
-                if last_segment.ident.name.as_str() == "cfg_if" {
-                    return true;
-                }
+/// Returns the spans of the `#[path]` attributes after the first one, which rustc ignores.
+fn ignored_path_attrs(attrs: &[ast::Attribute]) -> impl Iterator<Item = Span> + '_ {
+    attrs
+        .iter()
+        .filter(|attr| attr.has_name(sym::path))
+        .skip(1)
+        .map(|attr| attr.span)
+}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+fn display_chain(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+fn display_candidates(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("\n  - {}", path.display()))
+        .collect()
+}
+
+/// Moves the errors of `result`, if any, into `errors`.
+fn collect_errors(
+    errors: &mut Vec<ModuleResolutionError>,
+    result: Result<(), Vec<ModuleResolutionError>>,
+) {
+    if let Err(mut result_errors) = result {
+        errors.append(&mut result_errors);
+    }
+}
+
+fn errors_to_result(errors: Vec<ModuleResolutionError>) -> Result<(), Vec<ModuleResolutionError>> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::formatting::utils::mk_sp;
+
+    #[test]
+    fn multi_external_sources_are_sorted_by_path() {
+        let module = |pos| {
+            Module::new(
+                mk_sp(BytePos(pos), BytePos(pos)),
+                None,
+                None,
+                Cow::Owned(vec![]),
+                Cow::Owned(vec![]),
+            )
+        };
+        let ownership = DirectoryOwnership::Owned { relative: None };
+        let mods = vec![
+            (PathBuf::from("windows.rs"), ownership, module(0)),
+            (PathBuf::from("unix.rs"), ownership, module(1)),
+            (PathBuf::from("windows.rs"), ownership, module(2)),
+        ];
+
+        if let SubModKind::MultiExternal(mods) = SubModKind::multi_external(mods) {
+            let order: Vec<_> = mods
+                .iter()
+                .map(|(path, _, module)| (path.to_str().unwrap(), module.span.lo().0))
+                .collect();
+            assert_eq!(
+                order,
+                vec![("unix.rs", 1), ("windows.rs", 0), ("windows.rs", 2)]
+            );
+        } else {
+            panic!("Expected multiple external sources");
+        }
+    }
+
+    /// A `ModuleSession` whose files are kept in memory instead of being read from the disk.
+    struct InMemorySession {
+        parse_sess: ParseSess,
+        files: HashMap<PathBuf, &'static str>,
+        /// Paths that lead to another file, like symlinks.
+        links: HashMap<PathBuf, PathBuf>,
+    }
+
+    impl InMemorySession {
+        fn new(config: &Config, files: &[(&str, &'static str)], links: &[(&str, &str)]) -> Self {
+            InMemorySession {
+                parse_sess: ParseSess::new(config).unwrap(),
+                files: files
+                    .iter()
+                    .map(|&(path, source)| (PathBuf::from(path), source))
+                    .collect(),
+                links: links
+                    .iter()
+                    .map(|&(link, target)| (PathBuf::from(link), PathBuf::from(target)))
+                    .collect(),
             }
-            false
         }
-        _ => false,
+
+        fn parse_crate(&self, path: &Path) -> Result<ast::Crate, ParserError> {
+            let source = self
+                .files
+                .get(path)
+                .or_else(|| self.files.get(self.links.get(path)?))
+                .ok_or(ParserError::NoInput)?;
+            let file_name =
+                rustc_span::FileName::Real(rustc_span::RealFileName::Named(path.to_path_buf()));
+            rustc_parse::parse_crate_from_source_str(
+                file_name,
+                source.to_string(),
+                self.parse_sess.inner(),
+            )
+            .map_err(|mut e| {
+                e.cancel();
+                ParserError::ParseError
+            })
+        }
+    }
+
+    impl ModuleSession for InMemorySession {
+        fn span_to_filename(&self, span: Span) -> FileName {
+            self.parse_sess.span_to_filename(span)
+        }
+
+        fn is_file_parsed(&self, path: &Path) -> bool {
+            self.parse_sess.is_file_parsed(path)
+        }
+
+        fn default_submod_path(
+            &self,
+            id: symbol::Ident,
+            relative: Option<symbol::Ident>,
+            dir_path: &Path,
+        ) -> Result<ModulePathSuccess, ModError<'_>> {
+            let mut paths = candidate_paths(dir_path, id, relative).into_iter();
+            let (default_path, secondary_path) = (paths.next().unwrap(), paths.next().unwrap());
+            if self.files.contains_key(&default_path) {
+                Ok(ModulePathSuccess {
+                    file_path: default_path,
+                    dir_ownership: DirectoryOwnership::Owned { relative: Some(id) },
+                })
+            } else if self.files.contains_key(&secondary_path) {
+                Ok(ModulePathSuccess {
+                    file_path: secondary_path,
+                    dir_ownership: DirectoryOwnership::Owned { relative: None },
+                })
+            } else {
+                Err(ModError::FileNotFound(id, default_path, secondary_path))
+            }
+        }
+
+        fn parse_file_as_module(
+            &self,
+            path: &Path,
+            _span: Option<Span>,
+        ) -> Result<
+            (Vec<ast::Attribute>, Vec<rustc_ast::ptr::P<ast::Item>>, Span, bool),
+            ParserError,
+        > {
+            let krate = self.parse_crate(path)?;
+            Ok((krate.attrs, krate.items, krate.span, false))
+        }
+
+        fn exists(&self, path: &Path) -> bool {
+            self.is_file(path) || self.files.keys().any(|file| file.starts_with(path))
+        }
+
+        fn is_file(&self, path: &Path) -> bool {
+            self.files.contains_key(path) || self.links.contains_key(path)
+        }
+
+        fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+            match self.links.get(path) {
+                Some(target) => Ok(target.clone()),
+                None if self.files.contains_key(path) => Ok(path.to_path_buf()),
+                None => Err(io::ErrorKind::NotFound.into()),
+            }
+        }
+    }
+
+    fn children(tree: &ModuleTree, path: &str) -> Vec<FileName> {
+        tree.children(&FileName::Real(PathBuf::from(path)))
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn modules_are_resolved_through_an_in_memory_session() {
+        let config = Config::default();
+        rustc_span::with_session_globals(config.edition().into(), || {
+            let session = InMemorySession::new(
+                &config,
+                &[
+                    ("mem/lib.rs", "mod a;\nmod b {\n    mod c;\n}\n"),
+                    ("mem/a.rs", "mod d;\n"),
+                    ("mem/a/d.rs", ""),
+                    ("mem/b/c/mod.rs", ""),
+                ],
+                &[],
+            );
+            let krate = session.parse_crate(Path::new("mem/lib.rs")).unwrap();
+            let ownership = DirectoryOwnership::Owned { relative: None };
+            let (_, tree) = ModResolver::with_session(&session, &config, ownership, true)
+                .visit_crate_with_tree(&krate)
+                .unwrap();
+
+            let file = |path: &str| FileName::Real(PathBuf::from(path));
+            assert_eq!(
+                children(&tree, "mem/lib.rs"),
+                vec![file("mem/a.rs"), file("mem/b/c/mod.rs")]
+            );
+            assert_eq!(children(&tree, "mem/a.rs"), vec![file("mem/a/d.rs")]);
+        });
+    }
+
+    #[test]
+    fn multiple_candidates_are_found_through_an_in_memory_session() {
+        let config = Config::default();
+        rustc_span::with_session_globals(config.edition().into(), || {
+            let session = InMemorySession::new(
+                &config,
+                &[
+                    ("mem/lib.rs", "mod a;\n"),
+                    ("mem/a.rs", ""),
+                    ("mem/a/mod.rs", ""),
+                ],
+                &[],
+            );
+            let krate = session.parse_crate(Path::new("mem/lib.rs")).unwrap();
+            let ownership = DirectoryOwnership::Owned { relative: None };
+            let errors = ModResolver::with_session(&session, &config, ownership, true)
+                .visit_crate_with_tree(&krate)
+                .err()
+                .unwrap();
+
+            assert_eq!(errors.len(), 1);
+            match &errors[0].kind {
+                ModuleResolutionErrorKind::MultipleCandidates { paths } => assert_eq!(
+                    paths,
+                    &[PathBuf::from("mem/a.rs"), PathBuf::from("mem/a/mod.rs")]
+                ),
+                kind => panic!("unexpected error: {}", kind),
+            }
+        });
+    }
+
+    #[test]
+    fn links_are_followed_through_an_in_memory_session() {
+        let config = Config::default();
+        rustc_span::with_session_globals(config.edition().into(), || {
+            let session = InMemorySession::new(
+                &config,
+                &[
+                    ("mem/lib.rs", "mod a;\n#[path = \"b.rs\"]\nmod b;\n"),
+                    ("mem/a.rs", ""),
+                ],
+                &[("mem/b.rs", "mem/a.rs")],
+            );
+            let krate = session.parse_crate(Path::new("mem/lib.rs")).unwrap();
+            let ownership = DirectoryOwnership::Owned { relative: None };
+            let (_, tree) = ModResolver::with_session(&session, &config, ownership, true)
+                .visit_crate_with_tree(&krate)
+                .unwrap();
+
+            // `b.rs` leads to `a.rs`, which is already resolved.
+            assert_eq!(
+                children(&tree, "mem/lib.rs"),
+                vec![FileName::Real(PathBuf::from("mem/a.rs"))]
+            );
+        });
     }
 }
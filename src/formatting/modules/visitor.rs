@@ -1,24 +1,27 @@
 use rustc_ast::ast;
-use rustc_ast::visit::Visitor;
-use rustc_span::Symbol;
+use rustc_ast::visit::{self, Visitor};
+use rustc_span::{sym, Symbol};
 
+use crate::config::{ActiveCfgs, ModMacros};
 use crate::formatting::{attr::MetaVisitor, syntux::parser::Parser, syntux::session::ParseSess};
 
 pub(crate) struct ModItem {
     pub(crate) item: ast::Item,
 }
 
-/// Traverse `cfg_if!` macro and fetch modules.
+/// Traverse `cfg_if!` macro, and the macros listed in `mod_macros`, and fetch modules.
 pub(crate) struct CfgIfVisitor<'a> {
     parse_sess: &'a ParseSess,
+    mod_macros: &'a ModMacros,
     mods: Vec<ModItem>,
 }
 
 impl<'a> CfgIfVisitor<'a> {
-    pub(crate) fn new(parse_sess: &'a ParseSess) -> CfgIfVisitor<'a> {
+    pub(crate) fn new(parse_sess: &'a ParseSess, mod_macros: &'a ModMacros) -> CfgIfVisitor<'a> {
         CfgIfVisitor {
             mods: vec![],
             parse_sess,
+            mod_macros,
         }
     }
 
@@ -36,8 +39,8 @@ impl<'a, 'ast: 'a> Visitor<'ast> for CfgIfVisitor<'a> {
     }
 }
 
-impl<'a, 'ast: 'a> CfgIfVisitor<'a> {
-    fn visit_mac_inner(&mut self, mac: &'ast ast::MacCall) -> Result<(), &'static str> {
+impl<'a> CfgIfVisitor<'a> {
+    fn visit_mac_inner(&mut self, mac: &ast::MacCall) -> Result<(), &'static str> {
         // Support both:
         // ```
         // extern crate cfg_if;
@@ -49,22 +52,151 @@ impl<'a, 'ast: 'a> CfgIfVisitor<'a> {
         // extern crate cfg_if;
         // cfg_if! {..}
         // ```
-        match mac.path.segments.last() {
-            Some(last_segment) => {
-                if last_segment.ident.name != Symbol::intern("cfg_if") {
-                    return Err("Expected cfg_if");
+        let items = if is_cfg_if(&mac.path) {
+            Parser::parse_cfg_if(self.parse_sess, mac)?
+        } else if is_mod_macro(&mac.path, self.mod_macros) {
+            Parser::parse_mod_macro(self.parse_sess, mac)?
+        } else {
+            return Err("Expected cfg_if or a macro listed in `mod_macros`");
+        };
+
+        for item in items {
+            match item.kind {
+                // Module declarations may be nested in other macro calls.
+                ast::ItemKind::MacCall(ref mac) => {
+                    if let Err(e) = self.visit_mac_inner(mac) {
+                        debug!("{}", e);
+                    }
                 }
+                _ => self.mods.push(ModItem { item }),
             }
-            None => {
-                return Err("Expected cfg_if");
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn is_cfg_if(path: &ast::Path) -> bool {
+    match path.segments.last() {
+        Some(last_segment) => last_segment.ident.name == Symbol::intern("cfg_if"),
+        None => false,
+    }
+}
+
+pub(crate) fn is_include(path: &ast::Path) -> bool {
+    match path.segments.last() {
+        Some(last_segment) => last_segment.ident.name == Symbol::intern("include"),
+        None => false,
+    }
+}
+
+/// Returns `true` if `path` ends with one of the paths listed in `mod_macros`.
+pub(crate) fn is_mod_macro(path: &ast::Path, mod_macros: &ModMacros) -> bool {
+    mod_macros.iter().any(|mod_macro| {
+        let suffix: Vec<&str> = mod_macro.split("::").collect();
+        suffix.len() <= path.segments.len()
+            && path.segments[path.segments.len() - suffix.len()..]
+                .iter()
+                .zip(suffix)
+                .all(|(segment, name)| segment.ident.name.as_str() == name)
+    })
+}
+
+/// Collects the `mod` items declared directly in the bodies of functions and in the block
+/// initializers of constants and statics, including those of the items of impls and traits.
+#[derive(Default)]
+pub(crate) struct BlockModVisitor<'a> {
+    mods: Vec<&'a ast::Item>,
+}
+
+impl<'a> BlockModVisitor<'a> {
+    pub(crate) fn mods(self) -> Vec<&'a ast::Item> {
+        self.mods
+    }
+}
+
+impl<'a> Visitor<'a> for BlockModVisitor<'a> {
+    fn visit_item(&mut self, item: &'a ast::Item) {
+        match item.kind {
+            // The modules nested in this one are found when it is visited.
+            ast::ItemKind::Mod(..) => self.mods.push(item),
+            ast::ItemKind::Fn(..)
+            | ast::ItemKind::Const(..)
+            | ast::ItemKind::Static(..)
+            | ast::ItemKind::Impl(..)
+            | ast::ItemKind::Trait(..) => visit::walk_item(self, item),
+            _ => {}
+        }
+    }
+
+    fn visit_block(&mut self, block: &'a ast::Block) {
+        // Only the items of the body are looked at, not the expressions around them.
+        for stmt in &block.stmts {
+            if let ast::StmtKind::Item(ref item) = stmt.kind {
+                self.visit_item(item);
             }
+        }
+    }
+}
+
+/// Evaluates the predicate of a `#[cfg(..)]` attribute against a set of active cfg options.
+pub(crate) struct CfgVisitor<'a> {
+    active_cfgs: &'a ActiveCfgs,
+    /// Whether the visited predicate holds. Predicates that are not understood hold, so that
+    /// modules are only skipped when they are certainly inactive.
+    is_active: bool,
+}
+
+impl<'a> CfgVisitor<'a> {
+    pub(crate) fn new(active_cfgs: &'a ActiveCfgs) -> CfgVisitor<'a> {
+        CfgVisitor {
+            active_cfgs,
+            is_active: true,
+        }
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn eval_nested(&self, nm: &ast::NestedMetaItem) -> bool {
+        let mut visitor = CfgVisitor::new(self.active_cfgs);
+        visitor.visit_nested_meta_item(nm);
+        visitor.is_active
+    }
+}
+
+impl<'a, 'ast> MetaVisitor<'ast> for CfgVisitor<'a> {
+    fn visit_meta_list(
+        &mut self,
+        meta_item: &'ast ast::MetaItem,
+        list: &'ast [ast::NestedMetaItem],
+    ) {
+        let name = meta_item.name_or_empty();
+        self.is_active = if name == sym::all {
+            list.iter().all(|nm| self.eval_nested(nm))
+        } else if name == sym::any {
+            list.iter().any(|nm| self.eval_nested(nm))
+        } else if name == sym::not && list.len() == 1 {
+            !self.eval_nested(&list[0])
+        } else {
+            true
         };
+    }
 
-        let items = Parser::parse_cfg_if(self.parse_sess, mac)?;
-        self.mods
-            .append(&mut items.into_iter().map(|item| ModItem { item }).collect());
+    fn visit_meta_word(&mut self, meta_item: &'ast ast::MetaItem) {
+        self.is_active = self
+            .active_cfgs
+            .contains(&meta_item.name_or_empty().as_str(), None);
+    }
 
-        Ok(())
+    fn visit_meta_name_value(&mut self, meta_item: &'ast ast::MetaItem, lit: &'ast ast::Lit) {
+        self.is_active = match lit.kind {
+            ast::LitKind::Str(value, ..) => self
+                .active_cfgs
+                .contains(&meta_item.name_or_empty().as_str(), Some(&*value.as_str())),
+            _ => true,
+        };
     }
 }
 
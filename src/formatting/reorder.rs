@@ -11,8 +11,10 @@ use std::cmp::{Ord, Ordering};
 use rustc_ast::ast;
 use rustc_span::{symbol::sym, Span};
 
-use crate::config::{Config, GroupImportsTactic, ImportGranularity};
-use crate::formatting::imports::{flatten_use_trees, UseSegment};
+use crate::config::{
+    Config, GroupImportsTactic, ImportGranularity, ReexportsGroup, SelfImportsLayout,
+};
+use crate::formatting::imports::{flatten_use_trees, map_self_use_trees, UseSegment};
 use crate::formatting::modules::{get_mod_inner_attrs, FileModMap};
 use crate::formatting::{
     imports::{merge_use_trees, UseTree},
@@ -237,11 +239,33 @@ fn rewrite_reorderable_or_regroupable_items(
                 ImportGranularity::One => merge_use_trees(normalized_items, SharedPrefix::One),
                 ImportGranularity::Preserve => normalized_items,
             };
+            normalized_items = match context.config.self_imports_layout() {
+                SelfImportsLayout::Collapse => map_self_use_trees(normalized_items, |trees| {
+                    merge_use_trees(trees, SharedPrefix::Crate)
+                }),
+                SelfImportsLayout::Expand => {
+                    map_self_use_trees(normalized_items, flatten_use_trees)
+                }
+                SelfImportsLayout::Preserve => normalized_items,
+            };
 
             let mut regrouped_items = match context.config.group_imports() {
                 GroupImportsTactic::Preserve => vec![normalized_items],
                 GroupImportsTactic::StdExternalCrate => group_imports(normalized_items),
             };
+            regrouped_items = match context.config.group_reexports() {
+                ReexportsGroup::First => {
+                    let (reexports, mut imports) = split_reexports(regrouped_items);
+                    imports.insert(0, reexports);
+                    imports
+                }
+                ReexportsGroup::Last => {
+                    let (reexports, mut imports) = split_reexports(regrouped_items);
+                    imports.push(reexports);
+                    imports
+                }
+                ReexportsGroup::Preserve => regrouped_items,
+            };
 
             if context.config.reorder_imports() {
                 regrouped_items.iter_mut().for_each(|items| items.sort())
@@ -322,6 +346,22 @@ fn group_imports(uts: Vec<UseTree>) -> Vec<Vec<UseTree>> {
     vec![std_imports, external_imports, local_imports]
 }
 
+/// Takes the re-exports out of every group of imports and returns them as a
+/// separate group, together with the remaining groups.
+fn split_reexports(groups: Vec<Vec<UseTree>>) -> (Vec<UseTree>, Vec<Vec<UseTree>>) {
+    let mut reexports = Vec::new();
+    let imports = groups
+        .into_iter()
+        .map(|group| {
+            let (group_reexports, imports): (Vec<_>, _) =
+                group.into_iter().partition(UseTree::is_reexport);
+            reexports.extend(group_reexports);
+            imports
+        })
+        .collect();
+    (reexports, imports)
+}
+
 /// A simplified version of `ast::ItemKind`.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 enum ReorderableItemKind {
@@ -370,7 +410,10 @@ impl ReorderableItemKind {
             ReorderableItemKind::ExternCrate
             | ReorderableItemKind::Mod
             | ReorderableItemKind::Other => false,
-            ReorderableItemKind::Use => config.group_imports() != GroupImportsTactic::Preserve,
+            ReorderableItemKind::Use => {
+                config.group_imports() != GroupImportsTactic::Preserve
+                    || config.group_reexports() != ReexportsGroup::Preserve
+            }
         }
     }
 
@@ -383,6 +426,17 @@ impl ReorderableItemKind {
     }
 }
 
+/// Returns the minimum number of blank lines `blank_lines_around_mods` asks for between
+/// `prev` and `next`. Consecutive `mod foo;` declarations are kept together.
+fn blank_lines_around_mods(config: &Config, prev: Option<&ast::Item>, next: &ast::Item) -> usize {
+    let is_mod = |item: &ast::Item| matches!(item.kind, ast::ItemKind::Mod(..));
+    match prev {
+        Some(prev) if is_mod(prev) && is_mod(next) && is_mod_decl(prev) && is_mod_decl(next) => 0,
+        Some(prev) if is_mod(prev) || is_mod(next) => config.blank_lines_around_mods(),
+        _ => 0,
+    }
+}
+
 impl<'b, 'a: 'b> FmtVisitor<'a> {
     /// Format items with the same item kind and reorder them, regroup them, or
     /// both. If `in_group` is `true`, then the items separated by an empty line
@@ -436,7 +490,10 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
     /// Visits and format the given items. Items are reordered If they are
     /// consecutive and reorderable.
     pub(crate) fn visit_items_with_reordering(&mut self, mut items: &[&ast::Item]) {
+        let mut prev_item = None;
         while !items.is_empty() {
+            self.blank_lines_before_item =
+                blank_lines_around_mods(self.config, prev_item, items[0]);
             // If the next item is a `use`, `extern crate` or `mod`, then extract it and any
             // subsequent items that have the same item kind to be reordered within
             // `walk_reorderable_items`. Otherwise, just format the next item for output.
@@ -447,13 +504,15 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
                     item_kind,
                     item_kind.in_group(self.config),
                 );
-                let (_, rest) = items.split_at(visited_items_num);
+                let (visited, rest) = items.split_at(visited_items_num);
+                prev_item = visited.last().copied();
                 items = rest;
             } else {
                 // Reaching here means items were not reordered. There must be at least
                 // one item left in `items`, so calling `unwrap()` here is safe.
                 let (item, rest) = items.split_first().unwrap();
                 self.visit_item(item, true);
+                prev_item = Some(*item);
                 items = rest;
             }
         }
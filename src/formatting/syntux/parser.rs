@@ -1,3 +1,5 @@
+use std::fs::File;
+use std::io::Read;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
 
@@ -8,9 +10,9 @@ use rustc_parse::{
     new_parser_from_file,
     parser::{ForceCollect, Parser as RawParser},
 };
-use rustc_span::{sym, symbol::kw, Span};
+use rustc_span::{symbol::kw, Span};
 
-use crate::formatting::attr::first_attr_value_str_by_name;
+use crate::formatting::attr::{path_attr_value, MissingEnvVar};
 use crate::formatting::syntux::session::ParseSess;
 use crate::Input;
 
@@ -18,6 +20,8 @@ pub(crate) type DirectoryOwnership = rustc_expand::module::DirOwnership;
 pub(crate) type ModulePathSuccess = rustc_expand::module::ModulePathSuccess;
 pub(crate) type ModError<'a> = rustc_expand::module::ModError<'a>;
 
+pub(crate) const BYTE_ORDER_MARK: char = '\u{feff}';
+
 #[derive(Clone)]
 pub(crate) struct Directory {
     pub(crate) path: PathBuf,
@@ -85,7 +89,7 @@ impl<'a> ParserBuilder<'a> {
 }
 
 #[derive(Debug, PartialEq)]
-pub(crate) enum ParserError {
+pub enum ParserError {
     NoParseSess,
     NoInput,
     ParserCreationError,
@@ -94,8 +98,14 @@ pub(crate) enum ParserError {
 }
 
 impl<'a> Parser<'a> {
-    pub(crate) fn submod_path_from_attr(attrs: &[ast::Attribute], path: &Path) -> Option<PathBuf> {
-        let path_string = first_attr_value_str_by_name(attrs, sym::path)?.as_str();
+    pub(crate) fn submod_path_from_attr(
+        attrs: &[ast::Attribute],
+        path: &Path,
+    ) -> Option<Result<PathBuf, MissingEnvVar>> {
+        let path_string = match path_attr_value(attrs)? {
+            Ok(path_string) => path_string,
+            Err(e) => return Some(Err(e)),
+        };
         // On windows, the base path might have the form
         // `\\?\foo\bar` in which case it does not tolerate
         // mixed `/` and `\` separators, so canonicalize
@@ -103,14 +113,17 @@ impl<'a> Parser<'a> {
         #[cfg(windows)]
         let path_string = path_string.replace("/", "\\");
 
-        Some(path.join(&*path_string))
+        Some(Ok(path.join(&*path_string)))
     }
 
+    /// Parses the file at `path` as the body of a module. Also returns whether the file starts
+    /// with a byte order mark, which the source map strips when it loads the file.
     pub(crate) fn parse_file_as_module(
         sess: &'a ParseSess,
         path: &Path,
         span: Option<Span>,
-    ) -> Result<(Vec<ast::Attribute>, Vec<ptr::P<ast::Item>>, Span), ParserError> {
+    ) -> Result<(Vec<ast::Attribute>, Vec<ptr::P<ast::Item>>, Span, bool), ParserError> {
+        let has_byte_order_mark = Parser::has_byte_order_mark(path);
         let result = catch_unwind(AssertUnwindSafe(|| {
             let mut parser = new_parser_from_file(sess.inner(), &path, span);
             match parser.parse_mod(&TokenKind::Eof) {
@@ -125,14 +138,14 @@ impl<'a> Parser<'a> {
             }
         }));
         match result {
-            Ok(Some(m)) => {
+            Ok(Some((attrs, items, span))) => {
                 if !sess.has_errors() {
-                    return Ok(m);
+                    return Ok((attrs, items, span, has_byte_order_mark));
                 }
 
                 if sess.can_reset_errors() {
                     sess.reset_errors();
-                    return Ok(m);
+                    return Ok((attrs, items, span, has_byte_order_mark));
                 }
                 Err(ParserError::ParseError)
             }
@@ -142,6 +155,15 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Returns `true` if the file at `path` starts with a UTF-8 byte order mark.
+    pub(crate) fn has_byte_order_mark(path: &Path) -> bool {
+        let mut buf = [0; 3];
+        File::open(path)
+            .and_then(|mut file| file.read_exact(&mut buf))
+            .is_ok()
+            && std::str::from_utf8(&buf).map_or(false, |s| s.starts_with(BYTE_ORDER_MARK))
+    }
+
     pub(crate) fn parse_crate(
         input: Input,
         sess: &'a ParseSess,
@@ -188,6 +210,46 @@ impl<'a> Parser<'a> {
         }
     }
 
+    pub(crate) fn parse_mod_macro(
+        sess: &'a ParseSess,
+        mac: &'a ast::MacCall,
+    ) -> Result<Vec<ast::Item>, &'static str> {
+        match catch_unwind(AssertUnwindSafe(|| Parser::parse_mod_macro_inner(sess, mac))) {
+            Ok(items) => Ok(items),
+            Err(..) => Err("failed to parse the body of a module macro"),
+        }
+    }
+
+    /// Collects the module declarations and macro calls from the body of a macro which is not
+    /// known to rustfmt. The body may contain arbitrary tokens around the items, so tokens that
+    /// do not start an item are skipped.
+    fn parse_mod_macro_inner(sess: &'a ParseSess, mac: &'a ast::MacCall) -> Vec<ast::Item> {
+        let token_stream = mac.args.inner_tokens();
+        let mut parser = rustc_parse::stream_to_parser(sess.inner(), token_stream, Some(""));
+        let mut items = vec![];
+
+        while parser.token.kind != TokenKind::Eof {
+            let snapshot = parser.clone();
+            match parser.parse_item(ForceCollect::No) {
+                Ok(Some(item_ptr)) => {
+                    let item = item_ptr.into_inner();
+                    if let ast::ItemKind::Mod(..) | ast::ItemKind::MacCall(..) = item.kind {
+                        items.push(item);
+                    }
+                }
+                Ok(None) => parser.bump(),
+                Err(mut err) => {
+                    err.cancel();
+                    parser.sess.span_diagnostic.reset_err_count();
+                    parser = snapshot;
+                    parser.bump();
+                }
+            }
+        }
+
+        items
+    }
+
     fn parse_cfg_if_inner(
         sess: &'a ParseSess,
         mac: &'a ast::MacCall,
@@ -233,7 +295,7 @@ impl<'a> Parser<'a> {
                         );
                     }
                 };
-                if let ast::ItemKind::Mod(..) = item.kind {
+                if let ast::ItemKind::Mod(..) | ast::ItemKind::MacCall(..) = item.kind {
                     items.push(item);
                 }
             }
@@ -4,7 +4,7 @@ use std::ops::Deref;
 use rustc_ast::ast::{self, FnRetTy, Mutability};
 use rustc_span::{symbol::kw, symbol::Ident, BytePos, Pos, Span};
 
-use crate::config::{lists::*, IndentStyle, TypeDensity};
+use crate::config::{lists::*, Edition, IndentStyle, TypeDensity};
 use crate::formatting::{
     comment::{combine_strs_with_missing_comments, contains_comment},
     expr::{
@@ -43,7 +43,7 @@ pub(crate) fn rewrite_path(
     let skip_count = qself.map_or(0, |x| x.position);
 
     let mut result = if path.is_global() && qself.is_none() && path_context != PathContext::Import {
-        "::".to_owned()
+        global_path_prefix(context).to_owned()
     } else {
         String::new()
     };
@@ -59,7 +59,7 @@ pub(crate) fn rewrite_path(
         if skip_count > 0 {
             result.push_str(" as ");
             if path.is_global() && path_context != PathContext::Import {
-                result.push_str("::");
+                result.push_str(global_path_prefix(context));
             }
 
             // 3 = ">::".len()
@@ -91,6 +91,16 @@ pub(crate) fn rewrite_path(
     )
 }
 
+/// The prefix of a path starting from the crate root. In the 2015 edition `::foo` and
+/// `crate::foo` name the same item, so the former can be normalized to the latter.
+fn global_path_prefix(context: &RewriteContext<'_>) -> &'static str {
+    if context.config.normalize_crate_paths() && context.config.edition() == Edition::Edition2015 {
+        "crate::"
+    } else {
+        "::"
+    }
+}
+
 fn rewrite_path_segments<'a, I>(
     path_context: PathContext,
     mut buffer: String,
@@ -971,6 +981,11 @@ fn join_bounds_inner(
                 Some(ts) => contains_comment(context.snippet(ts)),
                 _ => false,
             };
+            // A `+` at the back goes before the trailing comment of the previous bound, so that
+            // it does not end up on a line of its own after the comment.
+            let separator_before_comment = prev_has_trailing_comment
+                && context.config.type_punctuation_density() == TypeDensity::Wide
+                && context.config.bound_separator() == SeparatorPlace::Back;
 
             let shape = if need_indent && force_newline {
                 shape
@@ -979,6 +994,15 @@ fn join_bounds_inner(
             } else {
                 shape
             };
+            // 2 = " +"
+            let shape = if force_newline
+                && i < items.len() - 1
+                && context.config.bound_separator() == SeparatorPlace::Back
+            {
+                shape.sub_width(2)?
+            } else {
+                shape
+            };
             let whitespace = if force_newline && (!prev_extendable || !generic_bounds_in_order) {
                 shape
                     .indent
@@ -988,16 +1012,22 @@ fn join_bounds_inner(
                 String::from(" ")
             };
 
-            let joiner = match context.config.type_punctuation_density() {
-                TypeDensity::Compressed => String::from("+"),
-                TypeDensity::Wide => whitespace + "+ ",
+            let joiner = match (
+                context.config.type_punctuation_density(),
+                context.config.bound_separator(),
+            ) {
+                (TypeDensity::Compressed, _) => String::from("+"),
+                (TypeDensity::Wide, SeparatorPlace::Front) => whitespace + "+ ",
+                (TypeDensity::Wide, SeparatorPlace::Back) => String::from(" +") + &whitespace,
             };
             let joiner = if has_leading_comment {
                 joiner.trim_end()
             } else {
                 &joiner
             };
-            let joiner = if prev_has_trailing_comment {
+            let joiner = if separator_before_comment {
+                ""
+            } else if prev_has_trailing_comment {
                 joiner.trim_start()
             } else {
                 joiner
@@ -1022,6 +1052,15 @@ fn join_bounds_inner(
                 }
             };
             match prev_trailing_span {
+                Some(ts) if separator_before_comment => combine_strs_with_missing_comments(
+                    context,
+                    &(strs + " +"),
+                    &trailing_str,
+                    ts,
+                    shape,
+                    true,
+                )
+                .map(|v| (v, trailing_span, extendable)),
                 Some(ts) if prev_has_trailing_comment => combine_strs_with_missing_comments(
                     context,
                     &strs,
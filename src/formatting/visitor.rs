@@ -90,6 +90,9 @@ pub(crate) struct FmtVisitor<'a> {
     pub(crate) skip_context: SkipContext,
     /// If set to `true`, normalize number of vertical spaces on formatting missing snippets.
     pub(crate) normalize_vertical_spaces: bool,
+    /// Minimum number of blank lines before the next item when normalizing vertical spaces,
+    /// if larger than `blank_lines_lower_bound`.
+    pub(crate) blank_lines_before_item: usize,
     /// If set to `true`, we are formatting a macro definition
     pub(crate) is_macro_def: bool,
 }
@@ -449,6 +452,12 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
                 return;
             }
 
+            if let Some(rw) = self.single_match_fn(&fn_str, fn_brace_style, block, inner_attrs) {
+                self.push_str(&rw);
+                self.last_pos = s.hi();
+                return;
+            }
+
             self.push_str(&fn_str);
             match fn_brace_style {
                 FnBraceStyle::SameLine => self.push_str(" "),
@@ -493,7 +502,7 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
                 }
             }
             // Module is inline, in this case we treat it like any other item.
-            _ if !is_mod_decl(item) => {
+            ast::ItemKind::Mod(..) if !is_mod_decl(item) => {
                 if self.visit_attrs(&item.attrs, ast::AttrStyle::Outer) {
                     self.push_skipped_with_span(item.attrs.as_slice(), item.span(), item.span());
                     false
@@ -907,6 +916,7 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
             report,
             skip_context: Default::default(),
             normalize_vertical_spaces: false,
+            blank_lines_before_item: 0,
             is_macro_def: false,
         }
     }
@@ -925,6 +935,22 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
 
     // Returns true if we should skip the following item.
     pub(crate) fn visit_attrs(&mut self, attrs: &[ast::Attribute], style: ast::AttrStyle) -> bool {
+        self.visit_attrs_inner(attrs, style, false)
+    }
+
+    /// Like `visit_attrs` for the inner attributes of the crate root, which are reordered when
+    /// `reorder_crate_attributes` is enabled.
+    fn visit_crate_attrs(&mut self, attrs: &[ast::Attribute]) -> bool {
+        let reorder = self.config.reorder_crate_attributes();
+        self.visit_attrs_inner(attrs, ast::AttrStyle::Inner, reorder)
+    }
+
+    fn visit_attrs_inner(
+        &mut self,
+        attrs: &[ast::Attribute],
+        style: ast::AttrStyle,
+        reorder: bool,
+    ) -> bool {
         for attr in attrs {
             if attr.has_name(depr_skip_annotation()) {
                 let file_name = self.parse_sess.span_to_filename(attr.span);
@@ -956,7 +982,15 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
             return false;
         }
 
-        let rewrite = attrs.rewrite(&self.get_context(), self.shape());
+        let rewrite = {
+            let context = self.get_context();
+            let reordered = if reorder {
+                rewrite_reordered_crate_attrs(&context, &attrs, self.shape())
+            } else {
+                None
+            };
+            reordered.or_else(|| attrs.rewrite(&context, self.shape()))
+        };
         let span = mk_sp(attrs[0].span.lo(), attrs[attrs.len() - 1].span.hi());
         self.push_rewrite(span, rewrite);
 
@@ -1061,11 +1095,20 @@ impl<'b, 'a: 'b> FmtVisitor<'a> {
 
     pub(crate) fn format_separate_mod(&mut self, m: &Module<'_>, end_pos: BytePos) {
         self.block_indent = Indent::empty();
-        let skipped = self.visit_attrs(m.attrs(), ast::AttrStyle::Inner);
+        let skipped = if m.is_crate_root() {
+            self.visit_crate_attrs(m.attrs())
+        } else {
+            self.visit_attrs(m.attrs(), ast::AttrStyle::Inner)
+        };
         assert!(
             !skipped,
             "Skipping module must be handled before reaching this line.",
         );
+        // Format the gap before the first item from where the inner attributes end in the
+        // source, so that the blank lines after them are kept.
+        if let Some(inner_attrs_end) = m.inner_attrs_end() {
+            self.last_pos = self.last_pos.max(inner_attrs_end);
+        }
 
         self.walk_mod_items(&m.items);
         self.format_missing_with_indent(end_pos);
@@ -16,9 +16,13 @@ pub use crate::config::{
 };
 pub use crate::emitter::rustfmt_diff::{ModifiedChunk, ModifiedLines};
 pub use crate::format_report_formatter::{FormatReportFormatter, FormatReportFormatterBuilder};
+pub use crate::formatting::modules::{ModResolver, ModuleSession, ModuleTree};
 pub use crate::formatting::report::{FormatReport, FormatResult};
+pub use crate::formatting::ParserError;
 
-pub(crate) use crate::formatting::format_input_inner;
+pub(crate) use crate::formatting::{
+    find_sub_module_paths_inner, format_input_inner, resolve_module_tree_inner,
+};
 use crate::{emitter::Verbosity, result::OperationError};
 
 #[cfg(feature = "config")]
@@ -57,6 +61,23 @@ pub fn format(
     )
 }
 
+/// Resolves the modules of the given input without formatting anything, and returns
+/// which files are reached from which through `mod` declarations.
+pub fn resolve_module_tree(input: Input, config: &Config) -> Result<ModuleTree, OperationError> {
+    resolve_module_tree_inner(input, config)
+}
+
+/// Returns the paths of the files that the `mod` declarations of the given crate root refer
+/// to, including those declared in inline modules and blocks. Unlike `resolve_module_tree`, the
+/// referenced files are not parsed, so their own sub-modules are not included. Every module
+/// whose file cannot be found is reported as an error.
+pub fn find_sub_module_paths(
+    input: Input,
+    config: &Config,
+) -> Result<Vec<PathBuf>, OperationError> {
+    find_sub_module_paths_inner(input, config)
+}
+
 pub fn format_inputs<'a>(
     inputs: impl Iterator<Item = (Input, &'a Config)>,
     operation_setting: OperationSetting,
@@ -30,6 +30,9 @@ pub enum ErrorKind {
     /// Failed to format macro calls.
     #[error("failed to format macro calls")]
     MacroFormatError,
+    /// A `#[path]` attribute is ignored because an earlier one on the same module wins.
+    #[error("ignored duplicate path attribute")]
+    DuplicatePathAttr,
 }
 
 /// Represents errors related to formatting issues.
@@ -89,6 +92,7 @@ impl FormatError {
             ErrorKind::TrailingWhitespace
             | ErrorKind::DeprecatedAttr
             | ErrorKind::BadAttr
+            | ErrorKind::DuplicatePathAttr
             | ErrorKind::LicenseCheck => {
                 let len = self.line_str.as_ref().map_or(0, |s| s.len());
                 let trailing_ws_start = self
@@ -114,6 +118,9 @@ pub enum OperationError {
     /// Error during module resolution.
     #[error("{0}")]
     ModuleResolutionError(#[from] ModuleResolutionError),
+    /// Errors during module resolution, when more than one module could not be resolved.
+    #[error("{}", display_module_resolution_errors(.0))]
+    ModuleResolutionErrors(Vec<ModuleResolutionError>),
     /// Invalid glob pattern in `ignore` configuration option.
     #[error("invalid glob pattern found in ignore list: {0}")]
     InvalidGlobPattern(ignore::Error),
@@ -130,4 +137,31 @@ impl OperationError {
     pub fn is_parse_error(&self) -> bool {
         matches!(self, OperationError::ParseError { .. })
     }
+
+    /// Returns the module resolution errors of either variant, or none for other errors.
+    pub fn module_resolution_errors(&self) -> &[ModuleResolutionError] {
+        match self {
+            OperationError::ModuleResolutionError(error) => std::slice::from_ref(error),
+            OperationError::ModuleResolutionErrors(errors) => errors,
+            _ => &[],
+        }
+    }
+}
+
+impl From<Vec<ModuleResolutionError>> for OperationError {
+    fn from(mut errors: Vec<ModuleResolutionError>) -> OperationError {
+        if errors.len() == 1 {
+            OperationError::ModuleResolutionError(errors.pop().unwrap())
+        } else {
+            OperationError::ModuleResolutionErrors(errors)
+        }
+    }
+}
+
+fn display_module_resolution_errors(errors: &[ModuleResolutionError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
 }
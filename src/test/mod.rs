@@ -42,6 +42,9 @@ const FILE_SKIP_LIST: &[&str] = &[
     "configs/recursive/disabled/foo.rs",
     "configs/recursive/enabled/foo.rs",
     "mods-relative-path/mod_b.rs",
+    // These files are modules declared in `configs/blank_lines_around_mods`.
+    "configs/blank_lines_around_mods/lorem/mod.rs",
+    "configs/blank_lines_around_mods/ipsum/mod.rs",
 ];
 
 fn init_log() {
@@ -3,7 +3,9 @@ use std::path::PathBuf;
 use super::{format_file, read_config};
 use crate::{
     formatting::modules::{ModuleResolutionError, ModuleResolutionErrorKind},
-    OperationError,
+    result::ErrorKind,
+    find_sub_module_paths, resolve_module_tree, Config, FileName, Input, OperationError,
+    OperationSetting,
 };
 
 #[test]
@@ -28,6 +30,396 @@ fn parser_errors_in_submods_are_surfaced() {
     }
 }
 
+#[test]
+fn all_module_resolution_errors_are_surfaced() {
+    let filename = "tests/parser/multiple-mod-errors/lib.rs";
+    let file = PathBuf::from(filename);
+    let (config, operation, _) = read_config(&file);
+    if let Err(err @ OperationError::ModuleResolutionErrors(..)) =
+        format_file(&file, operation, config)
+    {
+        let errors = err.module_resolution_errors();
+        let modules: Vec<_> = errors.iter().map(|e| e.module.as_str()).collect();
+        assert_eq!(modules, vec!["invalid", "missing", "nested"]);
+        assert!(matches!(errors[0].kind, ModuleResolutionErrorKind::ParseError { .. }));
+        if let ModuleResolutionErrorKind::NotFound { candidates } = &errors[1].kind {
+            assert_eq!(
+                candidates,
+                &vec![
+                    PathBuf::from("tests/parser/multiple-mod-errors/missing.rs"),
+                    PathBuf::from("tests/parser/multiple-mod-errors/missing/mod.rs"),
+                ]
+            );
+        } else {
+            panic!("Expected not found error");
+        }
+    } else {
+        panic!("Expected ModuleResolution operation error");
+    }
+}
+
+#[test]
+fn both_candidate_files_of_a_module_are_reported() {
+    let filename = "tests/parser/multiple-candidates/lib.rs";
+    let file = PathBuf::from(filename);
+    let (config, operation, _) = read_config(&file);
+    if let Err(OperationError::ModuleResolutionError(error)) =
+        format_file(&file, operation, config)
+    {
+        // `bar` has the same two candidates, but its `#[path]` attribute picks one of them.
+        assert_eq!(error.module, "foo");
+        if let ModuleResolutionErrorKind::MultipleCandidates { paths } = &error.kind {
+            assert_eq!(
+                paths,
+                &vec![
+                    PathBuf::from("tests/parser/multiple-candidates/foo.rs"),
+                    PathBuf::from("tests/parser/multiple-candidates/foo/mod.rs"),
+                ]
+            );
+        } else {
+            panic!("Expected multiple candidates error");
+        }
+    } else {
+        panic!("Expected ModuleResolution operation error");
+    }
+}
+
+#[test]
+fn ignored_path_attributes_are_reported() {
+    let file = PathBuf::from("tests/parser/duplicate-path-attrs/lib.rs");
+    let (config, operation, _) = read_config(&file);
+    let report = format_file(&file, operation, config).unwrap();
+    let lib = FileName::Real(file);
+    let (_, result) = report
+        .format_result()
+        .find(|(name, _)| **name == lib)
+        .unwrap();
+
+    // The first `#[path]` wins, so `foo.rs` is resolved and the second attribute is reported.
+    let lines: Vec<_> = result
+        .all_errors()
+        .filter(|e| e.kind() == ErrorKind::DuplicatePathAttr)
+        .map(|e| e.line_num())
+        .collect();
+    assert_eq!(lines, vec![Some(2)]);
+}
+
+#[test]
+fn module_cycles_are_reported() {
+    let dir = PathBuf::from("tests/parser/module-cycle");
+    if let Err(OperationError::ModuleResolutionError(error)) =
+        resolve_module_tree(Input::File(dir.join("lib.rs")), &Config::default())
+    {
+        assert_eq!(error.module, "root");
+        if let ModuleResolutionErrorKind::Cycle { path, chain } = &error.kind {
+            assert_eq!(path, &dir.join("lib.rs"));
+            assert_eq!(chain, &vec![dir.join("lib.rs"), dir.join("a.rs")]);
+        } else {
+            panic!("Expected module cycle error");
+        }
+    } else {
+        panic!("Expected ModuleResolution operation error");
+    }
+}
+
+#[test]
+fn modules_nested_too_deeply_are_reported() {
+    let file = PathBuf::from("tests/parser/deep-modules/lib.rs");
+    assert!(resolve_module_tree(Input::File(file.clone()), &Config::default()).is_ok());
+
+    let mut config = Config::default();
+    config.override_value("max_module_depth", "2");
+    if let Err(OperationError::ModuleResolutionError(error)) =
+        resolve_module_tree(Input::File(file), &config)
+    {
+        assert_eq!(error.module, "c");
+        if let ModuleResolutionErrorKind::DepthExceeded { limit } = error.kind {
+            assert_eq!(limit, 2);
+        } else {
+            panic!("Expected module depth error");
+        }
+    } else {
+        panic!("Expected ModuleResolution operation error");
+    }
+}
+
+#[test]
+fn module_tree_lists_files_reached_by_mod_declarations() {
+    let dir = PathBuf::from("tests/parser/module-tree");
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    let file = |path: &str| FileName::Real(dir.join(path));
+
+    let files: Vec<_> = tree.files().cloned().collect();
+    assert_eq!(
+        files,
+        vec![file("a/c.rs"), file("b.rs"), file("b/d.rs"), file("lib.rs")]
+    );
+    // `c` is declared inside the inline module `a`, so it is a child of `lib.rs`.
+    let children: Vec<_> = tree.children(&file("lib.rs")).cloned().collect();
+    assert_eq!(children, vec![file("a/c.rs"), file("b.rs")]);
+    let children: Vec<_> = tree.children(&file("b.rs")).cloned().collect();
+    assert_eq!(children, vec![file("b/d.rs")]);
+    assert_eq!(tree.children(&file("b/d.rs")).count(), 0);
+}
+
+#[test]
+fn modules_declared_in_blocks_are_resolved() {
+    let dir = PathBuf::from("tests/parser/mods-in-blocks");
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    let file = |path: &str| FileName::Real(dir.join(path));
+
+    let children: Vec<_> = tree.children(&file("lib.rs")).cloned().collect();
+    assert_eq!(
+        children,
+        vec![file("in_const.rs"), file("in_fn.rs"), file("in_impl.rs")]
+    );
+}
+
+#[test]
+fn modules_declared_in_inline_modules_are_resolved() {
+    let dir = PathBuf::from("tests/parser/inline-mods");
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    let file = |path: &str| FileName::Real(dir.join(path));
+
+    let children: Vec<_> = tree.children(&file("lib.rs")).cloned().collect();
+    assert_eq!(
+        children,
+        vec![
+            file("in_block/nested.rs"),
+            file("outer/inner/deep.rs"),
+            file("sub.rs")
+        ]
+    );
+    // Like in rustc, an inline module inside a block does not own a directory either, so the
+    // modules declared in it are not looked for under `sub/`.
+    let children: Vec<_> = tree.children(&file("sub.rs")).cloned().collect();
+    assert_eq!(children, vec![file("in_sub_block/nested.rs")]);
+}
+
+#[test]
+fn path_attributes_with_env_macros_are_evaluated() {
+    let file = PathBuf::from("tests/parser/env-path/lib.rs");
+    let tree = resolve_module_tree(Input::File(file.clone()), &Config::default()).unwrap();
+    let generated = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/parser/env-path/generated.rs");
+
+    let children: Vec<_> = tree.children(&FileName::Real(file)).cloned().collect();
+    assert_eq!(children, vec![FileName::Real(generated)]);
+}
+
+#[cfg(unix)]
+#[test]
+fn files_shared_by_several_modules_are_children_of_each_parent() {
+    let dir = PathBuf::from("tests/parser/shared-mods");
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    let file = |path: &str| FileName::Real(dir.join(path));
+
+    let files: Vec<_> = tree.files().cloned().collect();
+    assert_eq!(
+        files,
+        vec![file("a.rs"), file("b.rs"), file("lib.rs"), file("shared.rs")]
+    );
+    for parent in &["a.rs", "b.rs"] {
+        let children: Vec<_> = tree.children(&file(parent)).cloned().collect();
+        assert_eq!(children, vec![file("shared.rs")]);
+    }
+}
+
+#[test]
+fn symlinked_module_files_are_resolved_once() {
+    // `bar.rs` is a symlink to `foo.rs`.
+    let dir = PathBuf::from("tests/parser/symlinked-mods");
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    let file = |path: &str| FileName::Real(dir.join(path));
+
+    let files: Vec<_> = tree.files().cloned().collect();
+    assert_eq!(files, vec![file("foo.rs"), file("lib.rs")]);
+}
+
+#[test]
+fn sub_module_paths_are_found_without_parsing_them() {
+    let dir = PathBuf::from("tests/parser/module-tree");
+    let paths = find_sub_module_paths(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    // `b/d.rs` is declared in `b.rs`, which is not parsed.
+    assert_eq!(paths, vec![dir.join("b.rs"), dir.join("a/c.rs")]);
+
+    let dir = PathBuf::from("tests/parser/mods-in-blocks");
+    let paths = find_sub_module_paths(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    assert_eq!(
+        paths,
+        vec![dir.join("in_fn.rs"), dir.join("in_impl.rs"), dir.join("in_const.rs")]
+    );
+}
+
+#[test]
+fn sub_module_paths_that_cannot_be_found_are_reported() {
+    let dir = PathBuf::from("tests/parser/multiple-mod-errors");
+    let result = find_sub_module_paths(Input::File(dir.join("lib.rs")), &Config::default());
+    if let Err(OperationError::ModuleResolutionError(error)) = result {
+        // `invalid.rs` exists and is not parsed, so its parse error is not reported.
+        assert_eq!(error.module, "missing");
+    } else {
+        panic!("Expected ModuleResolution operation error");
+    }
+}
+
+#[test]
+fn modules_declared_in_mod_macros_are_resolved() {
+    let dir = PathBuf::from("tests/parser/mod-macros");
+    let mut config = Config::default();
+    config.override_value("mod_macros", "platform_mods, my_crate::nested_mods");
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &config).unwrap();
+    let file = |path: &str| FileName::Real(dir.join(path));
+
+    let children: Vec<_> = tree.children(&file("lib.rs")).cloned().collect();
+    assert_eq!(
+        children,
+        vec![
+            file("inner.rs"),
+            file("tests.rs"),
+            file("unix.rs"),
+            file("windows.rs"),
+        ]
+    );
+}
+
+#[test]
+fn modules_declared_in_included_files_are_resolved() {
+    let dir = PathBuf::from("tests/parser/include-macro");
+    let file = |path: &str| FileName::Real(dir.join(path));
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    assert_eq!(tree.children(&file("lib.rs")).count(), 0);
+
+    let mut config = Config::default();
+    config.override_value("include_macro_mods", "true");
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &config).unwrap();
+    let children: Vec<_> = tree.children(&file("lib.rs")).cloned().collect();
+    assert_eq!(children, vec![file("bar.rs"), file("foo.rs")]);
+}
+
+#[test]
+fn unsafe_modules_are_resolved_like_other_modules() {
+    // `unsafe mod` is only rejected after parsing, rustfmt must neither lose the files of such
+    // modules nor the `unsafe` keyword.
+    let dir = PathBuf::from("tests/parser/unsafe-mods");
+    let file = |path: &str| FileName::Real(dir.join(path));
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    let children: Vec<_> = tree.children(&file("lib.rs")).cloned().collect();
+    assert_eq!(children, vec![file("bar.rs"), file("baz/qux.rs"), file("foo.rs")]);
+
+    let operation = OperationSetting {
+        recursive: true,
+        ..OperationSetting::default()
+    };
+    let report = format_file(dir.join("lib.rs"), operation, Config::default()).unwrap();
+    let (_, result) = report
+        .format_result()
+        .find(|(path, _)| **path == file("lib.rs"))
+        .unwrap();
+    assert_eq!(
+        result.formatted_text(),
+        "pub unsafe mod bar;\nunsafe mod foo;\n\nmod baz {\n    unsafe mod qux;\n}\n"
+    );
+}
+
+#[test]
+fn modules_are_read_from_remapped_paths() {
+    let dir = PathBuf::from("tests/parser/path-remaps");
+    let file = |path: &str| FileName::Real(dir.join(path));
+    let mut config = Config::default();
+    // The longest matching prefix wins, `tests/parser` alone points nowhere.
+    config.override_value(
+        "module_path_remaps",
+        "tests/parser=missing, tests/parser/path-remaps/gen=tests/parser/path-remaps/vendor/gen",
+    );
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &config).unwrap();
+    let children: Vec<_> = tree.children(&file("lib.rs")).cloned().collect();
+    assert_eq!(children, vec![file("gen/mod.rs")]);
+    let children: Vec<_> = tree.children(&file("gen/mod.rs")).cloned().collect();
+    assert_eq!(children, vec![file("gen/foo.rs")]);
+
+    let operation = OperationSetting {
+        recursive: true,
+        ..OperationSetting::default()
+    };
+    let report = format_file(dir.join("lib.rs"), operation, config).unwrap();
+    let formatted: Vec<_> = report.format_result().map(|(path, _)| path.clone()).collect();
+    assert_eq!(
+        formatted,
+        vec![file("lib.rs"), file("vendor/gen/foo.rs"), file("vendor/gen/mod.rs")]
+    );
+}
+
+#[test]
+fn remapped_paths_in_config_files_are_relative_to_the_config_file() {
+    let dir = PathBuf::from("tests/parser/path-remaps");
+    let file = |path: &str| FileName::Real(dir.join(path));
+    let config =
+        Config::from_toml(r#"module_path_remaps = [["gen", "vendor/gen"]]"#, &dir).unwrap();
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &config).unwrap();
+    let children: Vec<_> = tree.children(&file("lib.rs")).cloned().collect();
+    assert_eq!(children, vec![file("gen/mod.rs")]);
+    let children: Vec<_> = tree.children(&file("gen/mod.rs")).cloned().collect();
+    assert_eq!(children, vec![file("gen/foo.rs")]);
+}
+
+#[test]
+fn every_cfg_attr_path_of_a_module_is_resolved() {
+    let dir = PathBuf::from("tests/parser/cfg-attr-paths");
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &Config::default()).unwrap();
+    let file = |path: &str| FileName::Real(dir.join(path));
+
+    let files: Vec<_> = tree.files().cloned().collect();
+    assert_eq!(files, vec![file("lib.rs"), file("unix.rs"), file("windows.rs")]);
+}
+
+#[test]
+fn modules_with_inactive_cfg_are_skipped() {
+    let dir = PathBuf::from("tests/parser/active-cfgs");
+    let mut config = Config::default();
+    config.override_value("active_cfgs", r#"unix, feature = "extra""#);
+    let tree = resolve_module_tree(Input::File(dir.join("lib.rs")), &config).unwrap();
+    let file = |path: &str| FileName::Real(dir.join(path));
+
+    let children: Vec<_> = tree.children(&file("lib.rs")).cloned().collect();
+    assert_eq!(children, vec![file("extra.rs"), file("unix.rs")]);
+}
+
+#[test]
+fn modules_with_cfg_are_resolved_without_active_cfgs() {
+    let file = PathBuf::from("tests/parser/active-cfgs/lib.rs");
+    if let Err(OperationError::ModuleResolutionErrors(errors)) =
+        resolve_module_tree(Input::File(file), &Config::default())
+    {
+        let modules: Vec<_> = errors.iter().map(|e| e.module.as_str()).collect();
+        assert_eq!(modules, vec!["windows", "fallback"]);
+    } else {
+        panic!("Expected ModuleResolution operation error");
+    }
+}
+
+#[test]
+fn byte_order_mark_of_crate_root_and_sub_modules_is_handled() {
+    let dir = PathBuf::from("tests/parser/byte-order-mark");
+    let operation = OperationSetting {
+        recursive: true,
+        ..OperationSetting::default()
+    };
+    for &preserve in &[false, true] {
+        let mut config = Config::default();
+        config.set().preserve_byte_order_mark(preserve);
+        let report = format_file(dir.join("lib.rs"), operation, config).unwrap();
+        assert_eq!(report.format_result().count(), 2);
+        for (_, result) in report.format_result() {
+            let original = result.original_text();
+            let without_bom = original.trim_start_matches('\u{feff}');
+            assert_ne!(original, without_bom);
+            let expected = if preserve { original } else { without_bom };
+            assert_eq!(result.formatted_text(), expected);
+        }
+    }
+}
+
 fn assert_parser_error(filename: &str, exp_panic: bool) {
     let file = PathBuf::from(filename);
     let (config, operation, _) = read_config(&file);
@@ -0,0 +1,11 @@
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[cfg(unix)]
+mod unix;
+
+#[cfg(not(unix))]
+mod fallback;
+
+#[cfg(any(test, feature = "extra"))]
+mod extra;
@@ -0,0 +1,5 @@
+﻿//! A module saved with a byte order mark.
+
+fn foo() {
+    println!("foo");
+}
@@ -0,0 +1,3 @@
+mod c {
+    mod d;
+}
@@ -0,0 +1,3 @@
+#[path = "foo.rs"]
+#[path = "bar.rs"]
+mod foo;
@@ -0,0 +1,6 @@
+#[path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/parser/env-path/generated.rs")]
+mod generated;
+
+// The environment variable is not set, so the module is skipped.
+#[path = concat!(env!("RUSTFMT_TEST_UNSET_VARIABLE"), "/missing.rs")]
+mod missing;
@@ -0,0 +1 @@
+include!("mod_list.rs");
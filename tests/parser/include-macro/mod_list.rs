@@ -0,0 +1,2 @@
+mod bar;
+mod foo;
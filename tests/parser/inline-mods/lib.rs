@@ -0,0 +1,13 @@
+mod outer {
+    mod inner {
+        mod deep;
+    }
+}
+
+mod sub;
+
+fn foo() {
+    mod in_block {
+        mod nested;
+    }
+}
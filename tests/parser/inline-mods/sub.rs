@@ -0,0 +1,5 @@
+fn bar() {
+    mod in_sub_block {
+        mod nested;
+    }
+}
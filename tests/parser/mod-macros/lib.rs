@@ -0,0 +1,21 @@
+platform_mods! {
+    unix => mod unix;
+    windows => mod windows;
+}
+
+my_crate::nested_mods! {
+    cfg_if::cfg_if! {
+        if #[cfg(test)] {
+            mod tests;
+        }
+    }
+
+    platform_mods! {
+        mod inner;
+    }
+}
+
+// Not listed in `mod_macros`, so `ignored.rs` is never looked for.
+other_mods! {
+    mod ignored;
+}
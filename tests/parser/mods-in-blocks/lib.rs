@@ -0,0 +1,14 @@
+fn foo() {
+    mod in_fn;
+}
+
+impl Foo {
+    fn bar() {
+        #[path = "in_impl.rs"]
+        mod renamed;
+    }
+}
+
+const C: () = {
+    mod in_const;
+};
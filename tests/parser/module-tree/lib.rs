@@ -0,0 +1,5 @@
+mod b;
+
+mod a {
+    mod c;
+}
@@ -0,0 +1,4 @@
+// rustfmt-recursive: true
+mod foo;
+#[path = "bar.rs"]
+mod bar;
@@ -0,0 +1,4 @@
+// rustfmt-recursive: true
+mod invalid;
+mod missing;
+mod valid;
@@ -0,0 +1,2 @@
+#[path = "shared.rs"]
+mod shared;
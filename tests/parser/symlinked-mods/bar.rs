@@ -0,0 +1 @@
+foo.rs
\ No newline at end of file
@@ -0,0 +1,2 @@
+mod foo;
+mod bar;
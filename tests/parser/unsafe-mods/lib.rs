@@ -0,0 +1,6 @@
+unsafe mod foo;
+pub unsafe mod bar;
+
+mod baz {
+    unsafe mod qux;
+}
@@ -0,0 +1,24 @@
+trait Shape {
+    /// The area of the shape.
+    fn area(&self) -> f64;
+
+
+    /// The perimeter of the shape.
+    fn perimeter(&self) -> f64;
+    /// A shape with no extent.
+    fn is_empty(&self) -> bool {
+        self.area() == 0.0
+    }
+}
+
+impl Shape for Square {
+    /// The area of the square.
+    fn area(&self) -> f64 { self.side * self.side }
+
+
+
+    /// The perimeter of the square.
+    fn perimeter(&self) -> f64 { 4.0 * self.side }
+    /// A square with no side.
+    fn is_empty(&self) -> bool { self.side == 0.0 }
+}
@@ -0,0 +1,22 @@
+// Attribute macros with arguments.
+
+#[my_proc_macro(a,b = "c",   d(e,f))]
+fn foo() {}
+
+#[my_proc_macro(first_argument_name, second_argument = "some string value", third_argument(nested, list))]
+fn bar() {}
+
+#[my_proc_macro(trailing, comma,)]
+struct Baz;
+
+// Arguments that are not meta items are left as is.
+#[route(GET,"/",  guard = |req| req.is_ok())]
+#[my_proc_macro(a  =>  b)]
+fn qux() {}
+
+// Meta-like arguments whose values are paths.
+#[my_proc_macro(name = value,other(  nested = Path::to::thing ),  "lit")]
+fn quux() {}
+
+#[my_proc_macro(first_argument = FirstValue, second_argument = some::path::to::SecondValue, third)]
+fn corge() {}
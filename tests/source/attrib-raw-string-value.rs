@@ -0,0 +1,12 @@
+// Raw string attribute values are kept exactly as written
+
+#[doc = r"raw \ string"]
+#[doc=r#"raw "quoted" string"#]
+#[doc = r##"contains "# and \n"##]
+#[doc = r"
+  multi-line raw string
+      with indentation \
+"]
+#[cfg_attr(feature = "docs", doc = r#"cfg'd "raw" doc"#)]
+#[deprecated(note = r"use `bar` instead \ please", since = "1.0")]
+fn foo() {}
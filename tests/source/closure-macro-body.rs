@@ -0,0 +1,7 @@
+fn main() {
+    let print = |x| { println!("{}", x); };
+    v.iter().for_each(|x| { println!("{}", x); });
+    v.iter().for_each(move |x| {
+        assert_eq!(x, expected);
+    });
+}
@@ -0,0 +1,15 @@
+// Spacing around `move` in closures and async blocks.
+
+fn main() {
+    let f = move||x;
+    let f = move   || x;
+    let f = move|x|x + 1;
+    let f = move  |x, y|  x + y;
+    let f = move |x: u32| -> u32 { x };
+
+    let fut = async   move {};
+    let fut = async move{ x.await };
+    let fut = async
+        move { x.await };
+    spawn(async  move { run().await });
+}
@@ -0,0 +1,22 @@
+// Closures with an explicit return type and a block body.
+
+fn main() {
+    let f = |x: i32| -> Result<i32, E> { let y = x + 1; Ok(y) };
+    let g = |x: i32, y: i32| -> Result<HashMap<String, Vec<SomeLongTypeName>>, SomeVeryLongErrorType> {
+        let z = x + y;
+        Ok(HashMap::new())
+    };
+    let h = |first_argument: SomeType, second_argument: SomeOtherType| -> Result<HashMap<String, Vec<SomeLongTypeName>>, SomeVeryLongErrorType> {
+        Ok(HashMap::new())
+    };
+    foo(|x: i32| -> Result<HashMap<String, Vec<SomeLongTypeName>>, SomeVeryLongErrorTypeName> {
+        Ok(HashMap::new())
+    });
+    let i = move |x: i32| -> Result<HashMap<String, Vec<SomeLongTypeNameeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee>>, SomeVeryLongErrorType> { Ok(HashMap::new()) };
+    {
+        let j = move |x: i32| -> Result<HashMap<String, Vec<SomeLongTypeNameeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee>>, SomeVeryLongErrorType> {
+            let y = x * 2;
+            Ok(HashMap::new())
+        };
+    }
+}
@@ -0,0 +1,11 @@
+// Closures with explicit return types that return closures.
+
+fn main() {
+    let add = |x|->impl Fn(i32)->i32{move |y| x + y};
+    let add = |x: i32| -> impl Fn(i32) -> i32 {
+        move |y| x + y
+    };
+    let boxed = |x: i32| -> Box<dyn Fn(i32) -> i32> { Box::new(move |y| x * y) };
+    let nested = |x: i32| -> impl Fn(i32) -> Box<dyn Fn(i32) -> i32> { move |y| Box::new(move |z| x + y + z) };
+    let with_stmts = |x: i32| -> impl Fn(i32) -> i32 { let offset = x * 2; move |y| offset + y };
+}
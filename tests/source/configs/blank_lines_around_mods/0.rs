@@ -0,0 +1,12 @@
+// rustfmt-blank_lines_around_mods: 0
+// Blank lines around modules
+
+use std::fmt;
+mod lorem;
+mod ipsum;
+struct Foo;
+mod amet {
+    fn consectetur() {}
+}
+mod adipiscing {}
+fn elit() {}
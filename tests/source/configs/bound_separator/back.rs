@@ -0,0 +1,6 @@
+// rustfmt-bound_separator: Back
+// Where to put `+` in wrapped bound lists
+
+fn foo<T: Clone + std::fmt::Debug + std::fmt::Display + PartialEq + Eq + PartialOrd + Ord + std::hash::Hash + Send + Sync + 'static>(t: T) {}
+
+fn bar<T, U>(t: T, u: U) where T: Clone + std::fmt::Debug + std::fmt::Display + PartialEq + Eq + PartialOrd + Ord + std::hash::Hash + Send + Sync + 'static, U: Copy {}
@@ -0,0 +1,18 @@
+// rustfmt-bound_separator: Back
+// Line comments between bounds
+
+fn foo<T>(t: T)
+where
+    T: Clone // Needed to copy the value.
+        + std::fmt::Debug
+        + Send,
+{
+}
+
+fn bar<T>(t: T)
+where
+    T: Clone + // Needed to copy the value.
+        std::fmt::Debug +
+        Send,
+{
+}
@@ -0,0 +1,14 @@
+// rustfmt-chain_first_call_on_head: false
+// Keep the first call of a wrapped chain on the head line
+
+fn main() {
+    let items = collection.iter().filter(|item| item.is_visible()).map(|item| item.name()).collect::<Vec<_>>();
+
+    let value = self.context.config.formatting_options().indentation.width_for_level(depth).unwrap_or_default();
+
+    let response = client.get(format!("https://example.com/api/v1/items/{}/details", item_id)).send().await?.json().await?;
+
+    let result = some_function_with_a_long_name(first_argument, second_argument).into_iter().map(Item::from).collect();
+
+    foo.bar.baz.qux.quux.corge.grault.garply.waldo.fred.plugh.xyzzy.thud.method_call().another();
+}
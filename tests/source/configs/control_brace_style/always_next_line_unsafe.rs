@@ -0,0 +1,12 @@
+// rustfmt-control_brace_style: AlwaysNextLine
+// Control brace style for unsafe blocks
+
+fn main() {
+    unsafe   { lorem(); ipsum(); }
+    unsafe
+    {
+        dolor();
+        sit();
+    }
+    let x = unsafe { lorem(); ipsum() };
+}
@@ -0,0 +1,18 @@
+// rustfmt-control_brace_style: ClosingNextLine
+// `else` on its own line throughout `if let` chains
+
+fn main() {
+    if let Some(x) = foo() { bar(x); } else { baz(); }
+
+    if let Some(x) = foo() {
+        bar(x);
+    } else if let Ok(y) = qux() {
+        bar(y);
+    } else if ready {
+        wait();
+    } else {
+        baz();
+    }
+
+    let value = if let Some(x) = foo() { x } else { 0 };
+}
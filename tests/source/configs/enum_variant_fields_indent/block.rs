@@ -0,0 +1,8 @@
+// rustfmt-enum_variant_fields_indent: Block
+// Fields of wide struct-like enum variants
+
+enum Shape {
+    Point { x: i32, y: i32 },
+    Rectangle { width: u32, height: u32, origin: Point, color: Color },
+    Polygon { vertices: Vec<Point>, fill_color: Option<Color>, stroke_width: f32, closed: bool },
+}
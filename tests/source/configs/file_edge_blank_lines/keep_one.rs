@@ -0,0 +1,9 @@
+// rustfmt-file_edge_blank_lines: KeepOne
+// Blank lines at the end of the file
+
+fn main() {
+    let x = 1;
+}
+
+
+
@@ -0,0 +1,20 @@
+// rustfmt-fn_single_match_body: false
+// Function bodies made of a single match or if
+
+fn lorem(ipsum: Option<usize>) -> usize {
+    match ipsum { Some(dolor) => dolor, None => 0 }
+}
+
+fn sit(amet: bool) -> &'static str {
+    if amet { "consectetur adipiscing elit sed do eiusmod" } else { "tempor incididunt ut labore" }
+}
+
+fn adipiscing(elit: u32) -> u32 {
+    // Comments keep the normal layout.
+    match elit { 0 => 1, _ => elit }
+}
+
+fn sed(elit: u32) -> u32 {
+    let elit = elit + 1;
+    match elit { 0 => 1, _ => elit }
+}
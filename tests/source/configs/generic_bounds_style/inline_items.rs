@@ -0,0 +1,41 @@
+// rustfmt-generic_bounds_style: Inline
+// Where clause predicates of items moved to the generic params
+
+impl<T> Foo for Bar<T>
+where
+    T: Clone,
+{
+    fn foo(&self) {}
+}
+
+trait Foo<T>: Sized
+where
+    T: Clone,
+{
+    fn foo(&self, t: T);
+}
+
+struct Foo<T, U>
+where
+    T: Clone,
+{
+    t: T,
+    u: U,
+}
+
+struct Bar<T>(T)
+where
+    T: Clone;
+
+enum Foo<T>
+where
+    T: Clone,
+{
+    A(T),
+    B,
+}
+
+type Foo<T>
+where
+    T: Clone,
+= Vec<T>;
@@ -0,0 +1,22 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds moved to the where clause
+
+fn foo<T: Clone + Debug, U>(t: T, u: U) {}
+
+fn bar<'a, 'b: 'a, T: ?Sized + 'a>(t: &'b T) -> &'a T
+where
+    'a: 'static,
+{
+    t
+}
+
+trait Baz {
+    fn baz<T: Into<String>>(t: T);
+}
+
+// Bounds are not moved when there are comments in the generics.
+fn qux<
+    // The element type.
+    T: Clone,
+>(t: T) {
+}
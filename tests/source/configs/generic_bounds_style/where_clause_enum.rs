@@ -0,0 +1,7 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds of enums moved to the where clause
+
+enum Foo<T: Clone> {
+    A(T),
+    B,
+}
@@ -0,0 +1,10 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds of impls moved to the where clause
+
+impl<T: Clone + Debug> Foo for Bar<T> {
+    fn foo(&self) {}
+}
+
+impl<'a, 'b: 'a, T: ?Sized> Baz<'a, 'b, T> {
+    fn baz(&self) {}
+}
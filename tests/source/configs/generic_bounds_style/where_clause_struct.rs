@@ -0,0 +1,9 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds of structs moved to the where clause
+
+struct Foo<T: Clone, U> {
+    t: T,
+    u: U,
+}
+
+struct Bar<T: Clone>(T);
@@ -0,0 +1,10 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds of traits moved to the where clause
+
+trait Foo<T: Clone>: Sized {
+    fn foo(&self, t: T);
+}
+
+trait Bar<T: Into<String>> where T: Debug {
+    fn bar(&self, t: T);
+}
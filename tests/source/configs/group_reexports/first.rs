@@ -0,0 +1,19 @@
+// rustfmt-group_reexports: First
+// Re-exports grouping
+
+use std::fmt;
+pub use crate::error::Error;
+use serde::Deserialize;
+#[cfg(feature = "json")]
+pub use crate::json::Json;
+pub(crate) use crate::util::helper;
+pub(super) use super::shared::{Context, State};
+
+use crate::config::Config;
+pub use self::reader::Reader;
+
+mod inner {
+    use super::Config;
+    pub use super::Reader;
+    use std::io;
+}
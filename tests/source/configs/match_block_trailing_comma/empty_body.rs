@@ -0,0 +1,17 @@
+// rustfmt-match_block_trailing_comma: true
+// Match arms with an empty body.
+
+fn main() {
+    match x {
+        A => {}
+        B => { }
+        C => {
+        }
+        D => (),
+        E if cond => {}
+        F => {
+            // A comment keeps the body expanded.
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,16 @@
+// rustfmt-normalize_crate_paths: true
+// rustfmt-edition: 2015
+// Normalize `::` paths to `crate::` paths
+use ::foo::bar;
+use ::baz as qux;
+use ::{a, b::c};
+use crate::foo::baz;
+use self::foo::quux;
+
+fn main() {
+    let x = ::foo::bar();
+    let y: ::foo::Bar = crate::foo::Bar::new();
+    let z = self::foo::bar();
+    let w = <T as ::foo::Trait>::f();
+    let v = ::std::mem::size_of::<::foo::Bar>();
+}
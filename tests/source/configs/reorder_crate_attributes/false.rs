@@ -0,0 +1,11 @@
+// rustfmt-reorder_crate_attributes: false
+//! A crate with mixed crate-root attributes.
+
+#![feature(lang_items)]
+#![deny(missing_docs)]
+#![feature(  start  )]
+#![no_std]
+#![allow(dead_code)]
+#![no_main]
+
+fn main() {}
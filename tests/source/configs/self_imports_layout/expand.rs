@@ -0,0 +1,11 @@
+// rustfmt-self_imports_layout: Expand
+
+use std::io;
+use self::{b, a};
+use super::qux;
+use self::d::e;
+pub use self::f::{g, h};
+use crate::foo::{self, bar};
+#[cfg(test)]
+use self::tests::{helper, fixture};
+use self::a::{y, x};
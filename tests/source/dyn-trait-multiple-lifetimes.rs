@@ -0,0 +1,14 @@
+// `dyn Trait` types with several lifetime bounds.
+
+struct S<'a, 'b> {
+    a: Box<dyn Trait + Send + 'a + 'b>,
+    b: Box<dyn  Trait+Send+'a+'b>,
+    c: Box<dyn SomeVeryLongTraitName<WithGenerics> + AnotherLongTraitName + Send + Sync + 'a + 'b>,
+    d: &'a (dyn Trait + Send + 'a + 'b),
+    e: Box<dyn SomeVeryLongTraitName<WithGenerics> + AnotherVeryLongTraitName + Send + Sync + 'a + 'b>,
+    f: Box<dyn SomeVeryLongTraitName<WithGenerics> + AnotherVeryLongTraitName + YetAnotherLongTraitName + Send + Sync + 'a + 'b>,
+}
+
+fn f<'a, 'b>() -> Box<dyn Fn(SomeArgumentType, AnotherArgumentType) -> ReturnType + Send + Sync + 'a + 'b> { todo!() }
+
+type T<'a, 'b> = Box<dyn SomeVeryLongTraitName<WithGenerics> + AnotherVeryLongTraitName + Send + Sync + 'a + 'b>;
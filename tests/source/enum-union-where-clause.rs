@@ -0,0 +1,15 @@
+// Where clauses on enums and unions.
+
+enum Foo<T> where T: Clone { A(T), B }
+
+enum Bar<T, U> where T: Clone + Send + Sync + 'static, U: Iterator<Item = T> + Default {
+    A(T),
+    B(U),
+}
+
+union Baz<T> where T: Copy { a: T, b: u32 }
+
+union Qux<T, U> where T: Copy + Send + Sync + 'static, U: Copy + Default + PartialEq {
+    a: T,
+    b: U,
+}
@@ -0,0 +1,11 @@
+// Trait objects in function pointer types.
+
+type Callback = fn( Box<dyn   Fn()> ) ->Box< dyn Fn() >;
+
+type Handler = fn(&dyn Any, Box<dyn Fn(u32)->u32+Send>) -> Option<Box<dyn Error + 'static>>;
+
+type LongCallback = fn(Box<dyn Fn(SomeLongArgumentType) -> SomeLongReturnType + Send + Sync>, &mut dyn Iterator<Item = u32>) -> Box<dyn Fn() + Send>;
+
+struct Foo {
+    f: fn(&mut dyn Write) -> Result<(), Box<dyn Error>>,
+}
@@ -0,0 +1,15 @@
+// Impl headers with long generic params and trait references.
+
+impl<T> Trait<T> for Wrapper<T> {}
+
+impl<SomeLongTypeParameter: Clone + Debug, AnotherLongTypeParameter: Send + Sync> SomeLongTraitName<SomeLongTypeParameter, AnotherLongTypeParameter> for Wrapper<SomeLongTypeParameter, AnotherLongTypeParameter> {
+    fn foo() {}
+}
+
+impl<T: Clone, U> SomeVeryLongTraitNameWithGenerics<T, U, SomeOtherTypeArgument> for Wrapper<T> {}
+
+impl<'a, T: Clone + Debug + Send + Sync + 'static, U: Iterator<Item = T> + ExactSizeIterator> Trait<T> for Wrapper<T> {}
+
+impl<T> SomeVeryLongTraitName<SomeVeryLongTypeArgument, AnotherVeryLongTypeArgument, YetAnotherTypeArg> for Wrapper<T> where T: Clone {}
+
+impl<FirstLongTypeParameter: Clone + Debug, SecondLongTypeParameter: Send + Sync, ThirdParameter: Default> SomeLongTraitName<FirstLongTypeParameter, SecondLongTypeParameter, ThirdParameter, FourthTypeArgument> for Wrapper<FirstLongTypeParameter> {}
@@ -0,0 +1,16 @@
+#![feature(type_alias_impl_trait)]
+
+// `impl Trait` as the value of an associated type.
+
+impl Iterator for Foo {
+    type Item=impl   Debug;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        None
+    }
+}
+
+impl Bar for Baz {
+    type Fut = impl Future<Output=u32>+Send;
+    type Long = impl Iterator<Item = u32> + DoubleEndedIterator + ExactSizeIterator + Send + Sync + 'static;
+}
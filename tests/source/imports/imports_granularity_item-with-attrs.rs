@@ -0,0 +1,6 @@
+// rustfmt-imports_granularity: Item
+
+#[cfg(test)]
+use foo::{a, b};
+/// Re-exports.
+pub use self::{c, d::{e, f}};
@@ -0,0 +1,11 @@
+// Long and chained index expressions.
+
+fn main() {
+    let element = matrix[row];
+    let element = matrix[row * cols + col];
+    let voxel = volume[i][j][k];
+
+    let element = matrix[current_row_index * number_of_columns_in_matrix + current_column_index + offset];
+    let element = matrix[row_index][column_index * number_of_channels_per_pixel + channel_index_value];
+    let element = matrices[layer][current_row_index * number_of_columns + current_column_index][channel];
+}
@@ -0,0 +1,14 @@
+//! Inner attributes followed by a blank line and a comment.
+#![allow(dead_code)]
+#![deny( missing_docs )]
+
+// The first item.
+fn main() {}
+
+mod inner {
+    #![allow(unused)]
+    #![deny( warnings )]
+
+    // The first item of an inline module.
+    fn f() {}
+}
@@ -0,0 +1,16 @@
+// Labeled loops used as expressions.
+
+fn main() {
+    let x = 'outer : loop { break 'outer 5; };
+
+    let y =   'search: loop {
+        for i in 0..10 {
+            if i == target {
+                break   'search i;
+            }
+        }
+        break 'search 0
+    };
+
+    let z = 'a: loop { loop { break 'a    (1, 2); } };
+}
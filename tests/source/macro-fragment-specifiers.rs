@@ -0,0 +1,14 @@
+// rustfmt-format_macro_matchers: true
+
+macro_rules! fragments {
+    ( $ name : ident , $ vis : vis $ ty : ty ; $ ( $ arg : expr ) , * ) => {};
+    ( $ lt : lifetime $ block : block $ ( $ pat : pat ) | + $ ( ; ) ? ) => {};
+    ( $ path : path => $ ( $ key : literal : $ value : expr ) ; * ) => {};
+}
+
+// Bodies with repetitions are kept as written, but their matchers are formatted.
+macro_rules! pairs {
+    ( $ ( $ key : expr => $ value : expr ) , * $ ( , ) ? ) => {
+        vec![$(($key, $value)),*]
+    };
+}
@@ -0,0 +1,26 @@
+// Marker attributes keep their position relative to doc comments and derives
+
+/// A configuration error.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    Io,
+    #[non_exhaustive]
+    Parse { line: usize },
+}
+
+#[non_exhaustive]
+/// Options for the formatter.
+#[derive(Debug,Default)]
+pub struct Options {
+    pub width: usize,
+}
+
+/// Returns the number of items.
+#[must_use]
+#[inline]
+pub fn count(  ) -> usize { 0 }
+
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone)]
+pub struct Iter<'a> { slice: &'a [u8] }
@@ -0,0 +1,16 @@
+// Match arms with an empty body.
+
+fn main() {
+    match x {
+        A => {}
+        B => { }
+        C => {
+        }
+        D => (),
+        E if cond => {}
+        F => {
+            // A comment keeps the body expanded.
+        }
+        _ => {}
+    }
+}
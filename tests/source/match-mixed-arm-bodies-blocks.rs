@@ -0,0 +1,31 @@
+// rustfmt-trailing_comma: Never
+// rustfmt-match_block_trailing_comma: true
+// rustfmt-match_arm_blocks: true
+// Block-bodied and expression-bodied arms mixed in a single match
+
+fn main() {
+    match x {
+        0 => { foo(); }
+        1 => bar(),
+        2 => {}
+        3 => unsafe { baz() }
+        4 => a_function_with_a_rather_long_name(first_argument, second_argument, third_argument, fourth),
+        5 => ()
+    }
+    match x {
+        0 => { foo(); }
+        _ => bar(),
+    }
+    match x {
+        0 => bar(),
+        _ => { foo(); },
+    }
+    match x {
+        0 => bar(),
+        _ => ()
+    }
+    match x {
+        0 => bar(),
+        _ => first_operand_with_a_long_name + second_operand_with_a_long_name + third_operand + fourth_operand,
+    }
+}
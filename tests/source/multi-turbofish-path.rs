@@ -0,0 +1,7 @@
+// Paths with several turbofish segments.
+
+fn main() {
+    let x = Foo :: < Bar > :: method :: < Baz > ( a , b );
+    let y = Foo::<Bar,Qux>::new::<'a,T>();
+    let z = HashMap::<String, Vec<u32>>::from_iter::<Vec<(String, Vec<u32>)>>(some_long_argument_name, another_argument);
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let (first_long_binding, (second_long_binding, third_long_binding), fourth_long_binding) = value;
+    let (a, (first_very_long_binding_name, second_very_long_binding_name, third_very_long_name), b) = v;
+}
@@ -0,0 +1,27 @@
+// Range patterns and rest patterns both use `..` but are spaced differently
+
+fn main() {
+    match x {
+        0 ..= 9 => {}
+        10..20 => {}
+        ..=-1 => {}
+        100 .. => {}
+        'a'..='z' | 'A' ..= 'Z' => {}
+        _ => {}
+    }
+
+    match point {
+        Point { x: 0..=9, .. } => {}
+        Point {..} => {}
+        Point { x, y: _, ..  } => {}
+    }
+
+    match slice {
+        [first, .., last] => {}
+        [x @ 1..=5, rest @ ..] => {}
+        [ .. ] => {}
+    }
+
+    let (a, ..) = tuple;
+    let Tuple(.., z) = t;
+}
@@ -0,0 +1,16 @@
+// `return` and `break` with values that do not fit on one line.
+
+fn foo() -> u32 {
+    if condition {
+        return compute_the_final_result(first_argument_with_long_name, second_argument_with_long_name, third);
+    }
+    let value = 'outer: loop {
+        loop {
+            break 'outer compute_the_final_result(first_argument_with_long_name, second_argument_with_long_name);
+        }
+    };
+    let value = loop {
+        break compute_the_final_result(first_argument_with_long_name, second_argument_with_long_name, third_value);
+    };
+    return vec![first_argument_with_long_name, second_argument_with_long_name, third_argument_value, fourth];
+}
@@ -0,0 +1,27 @@
+// `Self` in type, path and bound positions.
+
+trait Container: Sized where Self : Clone {
+    type Item ;
+    type Iter: Iterator<Item=Self::Item>;
+
+    fn get(&self, i: usize) -> Option<& Self::Item>;
+    fn first(&self) -> Option<&Self :: Item> {
+        < Self as Container >::get(self, 0)
+    }
+    fn new() -> Self;
+    fn boxed(self) -> Box< Self > {
+        Box::new(self)
+    }
+}
+
+impl Container for Vec<u32> {
+    type Item = u32;
+    type Iter = std::vec::IntoIter<< Self as Container >::Item>;
+
+    fn get(&self, i: usize) -> Option<&Self::Item> {
+        <[u32]>::get(self, i)
+    }
+    fn new() -> Self {
+        Self :: with_capacity(<Self as Default>::default().len())
+    }
+}
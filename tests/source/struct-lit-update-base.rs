@@ -0,0 +1,13 @@
+// rustfmt-trailing_comma: Always
+// The base of a struct update is indented like the fields and never gets a trailing comma.
+
+fn main() {
+    let x = Foo { first_field: 1, second_field: 2, third_field: 3, fourth_field: 4, ..Default::default() };
+    let y = Foo { a: 1, ..base };
+    let z = Foo {
+        first_field: some_long_variable_name_for_the_first_field,
+        second_field: another_long_variable_name_for_the_second_field,
+        ..
+        base
+    };
+}
@@ -0,0 +1,12 @@
+// Tuple structs with a where clause.
+
+struct Foo<T>(T) where T: Clone;
+
+pub struct Bar<T, U>(pub T, U)   where T: Clone,
+      U: Default ;
+
+struct Baz<T>(
+    T,
+    u32,
+) where
+    T: Iterator<Item = u32> + Clone + Send + Sync + 'static;
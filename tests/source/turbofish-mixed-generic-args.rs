@@ -0,0 +1,10 @@
+// Turbofish and generic args mixing lifetime, const and type arguments.
+
+fn main() {
+    let x = Foo::<'a,{N},T>::new();
+    let y = foo::<'a, {N + 1}, T, 3, -1, {some_const_fn()}>();
+    let z = Foo::<'static, { SOME_VERY_LONG_CONSTANT_NAME + ANOTHER_CONSTANT }, SomeLongTypeName, AnotherLongTypeName>::new();
+    let w: Foo<'a,{N},T> = Foo { a: 1 };
+    some_function_with_generics::<'a, 'b, { LONG_CONSTANT_EXPRESSION * 2 }, HashMap<String, Vec<u8>>, 42>(arg1, arg2);
+}
+fn f<'a, const N: usize, T>(x: Foo<'a, { N }, T>) -> Foo<'a,{ N * 2 },T> { todo!() }
@@ -0,0 +1,8 @@
+// Nested generic defaults in type alias parameter lists
+
+type Map<K, V = HashMap<K,K>> = BTreeMap<K, V>;
+type Map2<K, V=HashMap<K, Vec<K>>, S =   RandomState> = HashMap<K, V, S>;
+type LongerAliasName<Key, Value = HashMap<Key, Vec<Option<Key>>>, Hasher = BuildHasherDefault<FxHasher>> = HashMap<Key, Value, Hasher>;
+type LongerAliasNameWhere<Key, Value = HashMap<Key, Vec<Option<Key>>>> where Key: Clone + Debug + Hash = HashMap<Key, Value>;
+type Alias<K, V = HashMap<SomeVeryLongKeyTypeName, Vec<SomeVeryLongValueTypeName>, BuildHasherDefault<FxHasher>>> = Inner<K, V>;
+pub type Alias2<Value = HashMap<SomeVeryLongKeyTypeName, Vec<SomeVeryLongValueTypeName<Nested, Deeper, Deepest>>>> = Inner<Value>;
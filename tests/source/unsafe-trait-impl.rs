@@ -0,0 +1,13 @@
+unsafe   trait Foo { }
+
+pub  unsafe trait Bar: Send  {
+    fn bar(&self);
+}
+
+unsafe  impl Foo for Baz { }
+
+unsafe impl<T:Send> Send for Wrapper<T>{}
+
+unsafe impl  Bar for Baz {
+    fn bar(&self) {}
+}
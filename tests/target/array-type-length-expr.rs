@@ -0,0 +1,16 @@
+// Length expressions in array types
+
+struct Buffers<T, const N: usize> {
+    plain: [T; N],
+    constant: [u8; SIZE],
+    block: [T; { N + 1 }],
+    block_multiple: [T; { N * 2 }],
+    nested: [[f32; { COLS }]; ROWS],
+    computed:
+        [u8; std::mem::size_of::<SomeLongTypeName>() * ELEMENTS_PER_CHUNK + HEADER_LENGTH_IN_BYTES],
+    generic_arg: Foo<[T; { N - 1 }], { N + 1 }>,
+}
+
+fn zeroed<const N: usize>() -> [u8; { N * 2 }] {
+    [0; { N * 2 }]
+}
@@ -0,0 +1,33 @@
+// rustfmt-edition: 2018
+// `impl Trait` return types on async functions
+
+async fn short() -> impl Display {
+    1
+}
+
+async fn stream_of_items(
+    client: &Client,
+) -> impl Stream<Item = Result<Item, Error>> + Send + Unpin + 'static {
+    client.items()
+}
+
+pub async fn handler_with_a_long_name(
+    request: Request,
+    state: Arc<State>,
+) -> impl IntoResponse + Send {
+    todo!()
+}
+
+impl Service {
+    pub async fn call(
+        &self,
+        req: Request,
+    ) -> impl Future<Output = Result<Response<Body>, ServiceError>> + Send + '_ {
+        self.inner.call(req)
+    }
+}
+
+async fn bounds_too_long_for_one_line()
+-> impl Stream<Item = Result<Vec<LongItemName>, LongErrorName>> + Send + Sync + Unpin + 'static {
+    todo!()
+}
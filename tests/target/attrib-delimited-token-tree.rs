@@ -0,0 +1,38 @@
+// Attributes whose arguments are a brace- or bracket-delimited token tree.
+
+#[my_macro { a, b, c }]
+#[my_macro[a, b]]
+#[my_macro {}]
+#[my_macro[]]
+fn foo() {}
+
+#[my_macro {
+    first_item_name,
+    second_item_name,
+    third_item_name,
+    fourth_item_name,
+    fifth
+}]
+#[my_macro[
+    first_item_name,
+    second_item_name,
+    third_item_name,
+    fourth_item_name,
+    fifth_item,
+]]
+fn bar() {}
+
+#[my_macro { key = "value", other = 1 + 2 }]
+struct Foo {
+    #[field[x, y]]
+    x: u32,
+}
+
+// Token trees that are not comma-separated lists are left as is.
+#[my_macro { key => value; other => thing }]
+#[my_macro[  struct Foo;  ]]
+fn baz() {}
+
+mod m {
+    #![inner { a, b }]
+}
@@ -0,0 +1,37 @@
+// Blocks ending in a tail expression or in a statement.
+
+fn tail_expression() -> u32 {
+    let x = 1;
+    x + 1
+}
+
+fn tail_statement() {
+    let x = 1;
+    println!("{}", x);
+}
+
+fn tail_after_blank_lines() -> u32 {
+    let x = 1;
+
+    x + 1
+}
+
+fn unit_tail_expression() {
+    foo()
+}
+
+fn unit_tail_statement() {
+    foo();
+}
+
+fn nested() -> u32 {
+    let w = { 1 + 2 };
+    let y = {
+        let a = 1;
+        a * 2
+    };
+    let z = {
+        bar();
+    };
+    w + y
+}
@@ -0,0 +1,21 @@
+// Derives inside `cfg_attr` are formatted like other meta items. rustfmt does not
+// sort derives, so their order is kept as written.
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize, Clone))]
+#[cfg_attr(
+    feature = "serde",
+    derive(
+        Serialize,
+        Deserialize,
+        Clone,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord
+    )
+)]
+#[derive(Copy)]
+struct Foo;
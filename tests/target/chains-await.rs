@@ -0,0 +1,26 @@
+// Chains with `.await` links.
+
+async fn f() {
+    let x = foo().await.bar().await;
+    let y = client
+        .get(url)
+        .send()
+        .await?
+        .json::<Response>()
+        .await?
+        .items
+        .into_iter()
+        .next()
+        .await;
+    let z = some_object
+        .first_method_call()
+        .await
+        .second_method_call(argument)
+        .await
+        .third()
+        .await?;
+    let w = fetch_all_the_things_from_the_server(first_argument, second_argument)
+        .await
+        .unwrap();
+    foo.await.bar.await.baz.await;
+}
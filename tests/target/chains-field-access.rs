@@ -0,0 +1,38 @@
+// Chains mixing field accesses and method calls
+
+fn main() {
+    let short = obj.field.method().other_field.method2();
+
+    let value = self
+        .context
+        .config
+        .formatting_options()
+        .indentation
+        .width_for_level(depth)
+        .unwrap_or_default();
+
+    let name = request
+        .headers
+        .authorization
+        .as_ref()
+        .map(|auth| auth.user.name.clone())
+        .unwrap_or_else(|| anonymous_user_name());
+
+    let total = self
+        .state
+        .inventory
+        .items
+        .iter()
+        .filter(|item| item.in_stock)
+        .map(|item| item.price.amount)
+        .sum::<u64>();
+
+    self.parse_sess
+        .source_map
+        .span_to_snippet(span)
+        .ok()?
+        .trim()
+        .lines
+        .first_line
+        .len();
+}
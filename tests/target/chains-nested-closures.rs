@@ -0,0 +1,33 @@
+// Closures nested inside closures in builder chains
+
+fn main() {
+    let app = App::builder()
+        .name("demo")
+        .with(|b| {
+            b.inner(|c| {
+                c.set_width(80);
+                c.set_height(24)
+            })
+        })
+        .build();
+
+    let server = Server::new()
+        .route("/", |r| {
+            r.get(|req| {
+                let body = render_index(&req);
+                Response::ok().body(body)
+            })
+            .post(|req| handle_post(req))
+        })
+        .middleware(|m| {
+            m.before(|req| log_request(req)).after(|res| {
+                log_response(&res);
+                res
+            })
+        })
+        .bind("127.0.0.1:8080");
+
+    config
+        .section(|s| s.entry(|e| e.key("name").value("value")))
+        .finish();
+}
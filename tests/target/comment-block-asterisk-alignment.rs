@@ -0,0 +1,45 @@
+// Leading asterisks of multi-line block comments are aligned under the opening one.
+
+/*
+ * Javadoc style, aligned.
+ * Second line.
+ */
+fn a() {}
+
+/*
+ * Misaligned stars.
+ *   Second line with extra space.
+ *
+ */
+fn b() {}
+
+fn c() {
+    /*
+     * Inside a block, misaligned.
+     * Second.
+     */
+    let x = 1;
+}
+
+/* Not javadoc
+   just indented text
+*/
+fn d() {}
+
+/**
+ * Doc block comment with misaligned stars.
+ * Second.
+ */
+fn e() {}
+
+/* First line text
+ * misaligned star
+ * another
+ */
+fn f() {}
+
+fn g() {
+    let x = 1; /* trailing
+                * star comment
+                */
+}
@@ -0,0 +1,17 @@
+// rustfmt-blank_lines_around_mods: 1
+// Blank lines around modules
+
+use std::fmt;
+
+mod ipsum;
+mod lorem;
+
+struct Foo;
+
+mod amet {
+    fn consectetur() {}
+}
+
+mod adipiscing {}
+
+fn elit() {}
@@ -0,0 +1,13 @@
+// rustfmt-control_brace_style: AlwaysSameLine
+// Control brace style for unsafe blocks
+
+fn main() {
+    unsafe {
+        lorem();
+        ipsum();
+    }
+    unsafe {
+        dolor();
+        sit();
+    }
+}
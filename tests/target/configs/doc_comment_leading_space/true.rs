@@ -0,0 +1,22 @@
+// rustfmt-doc_comment_leading_space: true
+// Space after doc comment openers
+
+//! Crate docs.
+//!  - indented markdown
+
+/// Lorem ipsum.
+///
+/// * list item
+///    continued
+///
+/// ```
+///let x = 1;
+///    let y = 2;
+/// ```
+fn lorem() {}
+
+/// Already spaced.
+struct Ipsum {
+    /// Field docs.
+    dolor: u32,
+}
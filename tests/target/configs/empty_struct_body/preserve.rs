@@ -0,0 +1,24 @@
+// rustfmt-empty_struct_body: Preserve
+// Empty struct bodies
+
+struct Unit;
+
+pub struct Braced {}
+
+#[derive(Default)]
+pub(crate) struct BracedWithAttr {}
+
+struct Generic<'a> {}
+
+struct Commented {
+    // no fields yet
+}
+
+struct Tuple();
+
+union Empty {}
+
+enum E {
+    Unit,
+    Braced {},
+}
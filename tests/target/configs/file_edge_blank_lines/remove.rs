@@ -0,0 +1,6 @@
+// rustfmt-file_edge_blank_lines: Remove
+// Blank lines at the end of the file
+
+fn main() {
+    let x = 1;
+}
@@ -0,0 +1,10 @@
+// rustfmt-float_exponent_case: Preserve
+// Float exponent case
+
+fn main() {
+    let f1 = 1E10;
+    let f2 = 2.5e-3f64;
+    let f3 = 6.022_140E+23_f64;
+    let f4 = 1.0;
+    let h = 0xEE;
+}
@@ -0,0 +1,31 @@
+// rustfmt-generic_bounds_style: Inline
+// Where clause predicates moved to the generic params
+
+fn foo<T: Clone + Debug, U: ?Sized>(t: T, u: &U) {}
+
+fn bar<'a, 'b: 'a, T>(t: &'b T) -> &'a T {
+    t
+}
+
+// Predicates that do not bound a param without bounds stay in the where clause.
+fn baz<T: Clone>(t: T)
+where
+    T: Debug,
+{
+}
+
+fn qux<T>(t: T)
+where
+    Vec<T>: Debug,
+{
+}
+
+fn quux<F>(f: F)
+where
+    for<'a> F: Fn(&'a str),
+{
+}
+
+trait Corge {
+    fn corge<T: Into<String>>(t: T);
+}
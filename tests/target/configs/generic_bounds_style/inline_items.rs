@@ -0,0 +1,24 @@
+// rustfmt-generic_bounds_style: Inline
+// Where clause predicates of items moved to the generic params
+
+impl<T: Clone> Foo for Bar<T> {
+    fn foo(&self) {}
+}
+
+trait Foo<T: Clone>: Sized {
+    fn foo(&self, t: T);
+}
+
+struct Foo<T: Clone, U> {
+    t: T,
+    u: U,
+}
+
+struct Bar<T: Clone>(T);
+
+enum Foo<T: Clone> {
+    A(T),
+    B,
+}
+
+type Foo<T: Clone> = Vec<T>;
@@ -0,0 +1,11 @@
+// rustfmt-generic_bounds_style: Inline
+// rustfmt-trailing_comma: Never
+// Generic params that no longer fit on one line
+
+fn lorem<
+    Ipsum: Clone + Debug + PartialEq,
+    Dolor: Clone + Debug + PartialEq,
+    Sit: Clone + Debug + PartialEq,
+    Amet: Clone + Debug + PartialEq
+>(ipsum: Ipsum, dolor: Dolor, sit: Sit, amet: Amet) {
+}
@@ -0,0 +1,30 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds moved to the where clause
+
+fn foo<T, U>(t: T, u: U)
+where
+    T: Clone + Debug,
+{
+}
+
+fn bar<'a, 'b, T>(t: &'b T) -> &'a T
+where
+    'b: 'a,
+    T: ?Sized + 'a,
+    'a: 'static,
+{
+    t
+}
+
+trait Baz {
+    fn baz<T>(t: T)
+    where
+        T: Into<String>;
+}
+
+// Bounds are not moved when there are comments in the generics.
+fn qux<
+    // The element type.
+    T: Clone,
+>(t: T) {
+}
@@ -0,0 +1,17 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds of impls moved to the where clause
+
+impl<T> Foo for Bar<T>
+where
+    T: Clone + Debug,
+{
+    fn foo(&self) {}
+}
+
+impl<'a, 'b, T> Baz<'a, 'b, T>
+where
+    'b: 'a,
+    T: ?Sized,
+{
+    fn baz(&self) {}
+}
@@ -0,0 +1,14 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds of structs moved to the where clause
+
+struct Foo<T, U>
+where
+    T: Clone,
+{
+    t: T,
+    u: U,
+}
+
+struct Bar<T>(T)
+where
+    T: Clone;
@@ -0,0 +1,17 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds of traits moved to the where clause
+
+trait Foo<T>: Sized
+where
+    T: Clone,
+{
+    fn foo(&self, t: T);
+}
+
+trait Bar<T>
+where
+    T: Into<String>,
+    T: Debug,
+{
+    fn bar(&self, t: T);
+}
@@ -0,0 +1,7 @@
+// rustfmt-generic_bounds_style: WhereClause
+// Generic bounds of type aliases moved to the where clause
+
+type Foo<T>
+where
+    T: Clone,
+= Vec<T>;
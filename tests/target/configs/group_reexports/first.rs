@@ -0,0 +1,22 @@
+// rustfmt-group_reexports: First
+// Re-exports grouping
+
+pub(super) use super::shared::{Context, State};
+pub use crate::error::Error;
+#[cfg(feature = "json")]
+pub use crate::json::Json;
+pub(crate) use crate::util::helper;
+
+use serde::Deserialize;
+use std::fmt;
+
+pub use self::reader::Reader;
+
+use crate::config::Config;
+
+mod inner {
+    pub use super::Reader;
+
+    use super::Config;
+    use std::io;
+}
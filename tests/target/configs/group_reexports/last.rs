@@ -0,0 +1,22 @@
+// rustfmt-group_reexports: Last
+// Re-exports grouping
+
+use serde::Deserialize;
+use std::fmt;
+
+pub(super) use super::shared::{Context, State};
+pub use crate::error::Error;
+#[cfg(feature = "json")]
+pub use crate::json::Json;
+pub(crate) use crate::util::helper;
+
+use crate::config::Config;
+
+pub use self::reader::Reader;
+
+mod inner {
+    use super::Config;
+    use std::io;
+
+    pub use super::Reader;
+}
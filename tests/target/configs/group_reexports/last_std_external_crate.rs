@@ -0,0 +1,24 @@
+// rustfmt-group_reexports: Last
+// rustfmt-group_imports: StdExternalCrate
+// Re-exports grouping
+
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::config::Config;
+
+pub use self::reader::Reader;
+pub(super) use super::shared::{Context, State};
+pub use crate::error::Error;
+#[cfg(feature = "json")]
+pub use crate::json::Json;
+pub(crate) use crate::util::helper;
+
+mod inner {
+    use std::io;
+
+    use super::Config;
+
+    pub use super::Reader;
+}
@@ -0,0 +1,19 @@
+// rustfmt-group_reexports: Preserve
+// Re-exports grouping
+
+pub(super) use super::shared::{Context, State};
+pub use crate::error::Error;
+#[cfg(feature = "json")]
+pub use crate::json::Json;
+pub(crate) use crate::util::helper;
+use serde::Deserialize;
+use std::fmt;
+
+pub use self::reader::Reader;
+use crate::config::Config;
+
+mod inner {
+    use super::Config;
+    pub use super::Reader;
+    use std::io;
+}
@@ -0,0 +1,10 @@
+// rustfmt-hex_literal_case: Upper
+// Hexadecimal literal case
+
+fn main() {
+    let h1 = 0xCAFE_BABE;
+    let h2 = 0xFFu8;
+    let h3 = 0x7FFF_FFFF_i32;
+    let d = 1234u32;
+    let f = 1e10;
+}
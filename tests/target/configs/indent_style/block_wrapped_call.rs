@@ -0,0 +1,17 @@
+// rustfmt-indent_style: Block
+// Wrapped function call arguments
+
+fn main() {
+    lorem(
+        dolor_sit_amet,
+        consectetur_adipiscing,
+        sed_do_eiusmod_tempor,
+        magna,
+    );
+    let result = consectetur(
+        adipiscing_elit,
+        sed_do_eiusmod,
+        tempor_incididunt,
+        ut_labore_et_dolore,
+    );
+}
@@ -0,0 +1,22 @@
+// rustfmt-javadoc_block_comments: false
+// Javadoc block comments
+
+/* Lorem ipsum
+   dolor sit amet
+*/
+fn adipiscing() {}
+
+/**
+   Consectetur adipiscing elit,
+   sed do eiusmod tempor.
+ */
+fn incididunt() {
+    /* Ut labore
+       et dolore */
+    let magna = 1;
+}
+
+/*
+ * Already javadoc style.
+ */
+fn aliqua() {}
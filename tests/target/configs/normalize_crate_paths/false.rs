@@ -0,0 +1,16 @@
+// rustfmt-normalize_crate_paths: false
+// rustfmt-edition: 2015
+// Do not normalize `::` paths to `crate::` paths
+use self::foo::quux;
+use crate::foo::baz;
+use baz as qux;
+use foo::bar;
+use {a, b::c};
+
+fn main() {
+    let x = ::foo::bar();
+    let y: ::foo::Bar = crate::foo::Bar::new();
+    let z = self::foo::bar();
+    let w = <T as ::foo::Trait>::f();
+    let v = ::std::mem::size_of::<::foo::Bar>();
+}
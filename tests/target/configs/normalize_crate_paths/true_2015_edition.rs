@@ -0,0 +1,16 @@
+// rustfmt-normalize_crate_paths: true
+// rustfmt-edition: 2015
+// Normalize `::` paths to `crate::` paths
+use self::foo::quux;
+use crate::baz as qux;
+use crate::foo::bar;
+use crate::foo::baz;
+use crate::{a, b::c};
+
+fn main() {
+    let x = crate::foo::bar();
+    let y: crate::foo::Bar = crate::foo::Bar::new();
+    let z = self::foo::bar();
+    let w = <T as crate::foo::Trait>::f();
+    let v = crate::std::mem::size_of::<crate::foo::Bar>();
+}
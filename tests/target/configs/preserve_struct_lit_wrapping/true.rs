@@ -0,0 +1,21 @@
+// rustfmt-preserve_struct_lit_wrapping: true
+// Struct literals that were written multi-line although they fit on one line.
+
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    let origin = Point {
+        x: 0,
+        y: 0,
+    };
+    let nested = Line {
+        start: Point { x: 0, y: 0 },
+        end: Point {
+            x: 1,
+            y: 1,
+        },
+    };
+    let with_base = Point {
+        x: 3,
+        ..origin
+    };
+}
@@ -0,0 +1,11 @@
+// rustfmt-reorder_crate_attributes: true
+//! A crate with mixed crate-root attributes.
+
+#![no_std]
+#![no_main]
+#![feature(lang_items)]
+#![feature(start)]
+#![deny(missing_docs)]
+#![allow(dead_code)]
+
+fn main() {}
@@ -0,0 +1,14 @@
+// rustfmt-self_imports_layout: Collapse
+
+pub use self::f::{g, h};
+#[cfg(test)]
+use self::tests::{fixture, helper};
+use self::{
+    a,
+    a::{x, y},
+    b,
+    d::e,
+};
+use super::qux;
+use crate::foo::{self, bar};
+use std::io;
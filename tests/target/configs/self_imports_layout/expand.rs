@@ -0,0 +1,14 @@
+// rustfmt-self_imports_layout: Expand
+
+use self::a;
+use self::a::x;
+use self::a::y;
+use self::b;
+use self::d::e;
+pub use self::f::g;
+pub use self::f::h;
+#[cfg(test)]
+use self::tests::{fixture, helper};
+use super::qux;
+use crate::foo::{self, bar};
+use std::io;
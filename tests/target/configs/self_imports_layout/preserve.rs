@@ -0,0 +1,11 @@
+// rustfmt-self_imports_layout: Preserve
+
+use self::a::{x, y};
+use self::d::e;
+pub use self::f::{g, h};
+#[cfg(test)]
+use self::tests::{fixture, helper};
+use self::{a, b};
+use super::qux;
+use crate::foo::{self, bar};
+use std::io;
@@ -0,0 +1,11 @@
+// rustfmt-unwrap_match_arm_blocks: true
+// Match arms whose body is a block
+
+fn main() {
+    match x {
+        Some(x) => foo(x),
+        None => {
+            bar();
+        }
+    }
+}
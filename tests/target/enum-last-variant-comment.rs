@@ -0,0 +1,26 @@
+// Comments on the last variant of an enum.
+
+enum Foo {
+    A,
+    /// The last variant.
+    B,
+}
+
+enum Bar {
+    /// The first variant.
+    A,
+
+    /// The last variant, after a blank line.
+    B(u32),
+}
+
+enum Baz {
+    A,
+    B, // A trailing comment on the last variant.
+}
+
+enum Qux {
+    A,
+    B,
+    // A comment before the closing brace.
+}
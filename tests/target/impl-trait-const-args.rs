@@ -0,0 +1,9 @@
+// Const arguments in `impl Trait`.
+// Associated const bindings, like `impl Trait<ASSOC = { 5 }>`, are not accepted by the parser
+// yet, so only const arguments are covered here.
+
+fn foo() -> impl Trait<{ 5 }> {}
+
+fn bar(x: impl Trait<{ N + 1 }, Output = [u8; 4]>) {}
+
+fn baz() -> Box<dyn Trait<{ N * 2 }>> {}
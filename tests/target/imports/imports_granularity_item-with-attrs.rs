@@ -0,0 +1,6 @@
+// rustfmt-imports_granularity: Item
+
+/// Re-exports.
+pub use self::{c, d::{e, f}};
+#[cfg(test)]
+use foo::{a, b};
@@ -0,0 +1,13 @@
+#![feature(inline_const)]
+
+fn foo(x: u32) {
+    match x {
+        const { 1 + 2 } => {}
+        const { FOO } | 0 => {}
+        const {
+            let y = 3;
+            y * 2
+        } => {}
+        _ => {}
+    }
+}
@@ -0,0 +1,52 @@
+// Doc comments on the last item before a closing brace.
+
+impl Foo {
+    fn first(&self) {}
+
+    /// Last method.
+    fn last(&self) {}
+
+    // trailing comment after a blank line
+}
+
+impl Foo {
+    fn first(&self) {}
+
+    /** Block doc on the last method. */
+    fn last(&self) {}
+}
+
+impl Foo {
+    fn first(&self) {}
+
+    /// Doc then attribute.
+    #[inline]
+    fn last(&self) {}
+}
+
+impl Foo {
+    /// Only method.
+    fn only(&self) {}
+}
+
+trait T {
+    fn first(&self);
+
+    /// Last with default body.
+    fn last(&self) {}
+}
+
+fn main() {
+    struct S;
+    impl S {
+        /// Nested impl.
+        fn last(&self) {}
+    }
+}
+
+mod m {
+    fn first() {}
+
+    /// Last item in a module.
+    pub struct S;
+}
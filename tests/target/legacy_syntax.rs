@@ -0,0 +1,46 @@
+// rustfmt-edition: 2015
+// Legacy and unstable syntax that is still accepted by the parser
+
+#![feature(box_syntax, box_patterns)]
+
+fn box_expressions() {
+    let x = box 5;
+    let y: Box<Box<i32>> = box box 1;
+    let z = box (1, 2);
+    let w = box /* comment */ 5;
+    let v = box // comment
+        5;
+    let long = box SomeVeryLongTypeName::with_a_constructor(first_argument, second_argument_value);
+    let chained = box values
+        .iter()
+        .map(|v| v * 2)
+        .filter(|v| v % 3 == 0)
+        .collect::<Vec<_>>();
+    let commented = box /* comment */ values
+        .iter()
+        .map(|v| v * 2)
+        .filter(|v| v % 3 == 0)
+        .collect::<Vec<_>>();
+}
+
+fn box_patterns(x: Box<Option<Box<i32>>>) {
+    match x {
+        box Some(box ref n) => {}
+        box None => {}
+    }
+}
+
+fn legacy_range_patterns(n: u8) -> u8 {
+    match n {
+        0...9 => 0,
+        10...99 => 1,
+        _ => 2,
+    }
+}
+
+fn bare_trait_objects(f: &Fn(i32) -> i32, g: Box<Iterator<Item = u8> + Send>) {}
+
+fn try_macro() -> Result<(), Error> {
+    let file = try!(File::open("foo"));
+    Ok(())
+}
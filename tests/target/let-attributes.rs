@@ -0,0 +1,28 @@
+// Attributes on `let` statements
+
+fn main() {
+    #[allow(unused)]
+    let x = 1;
+
+    #[allow(unused_variables)]
+    let y: u32 = compute();
+
+    #[cfg(feature = "std")]
+    #[allow(unused_mut)]
+    let mut buffer = Vec::with_capacity(1024);
+
+    #[rustfmt::skip]
+    let matrix = [
+        1, 0,
+        0, 1,
+    ];
+
+    #[allow(unused)]
+    // A comment between the attribute and the binding
+    let z = 3;
+
+    if condition {
+        #[allow(clippy::let_unit_value)]
+        let unit = do_something();
+    }
+}
@@ -0,0 +1,20 @@
+// Closures as match arm bodies.
+
+fn main() {
+    let f = match op {
+        Op::Add => |x| x + 1,
+        Op::Mul => |x: u32| -> u32 { x * 2 },
+        Op::Long => |first_argument, second_argument| {
+            first_argument.some_method(second_argument).another_method()
+        },
+        Op::Block => |x| {
+            let y = x + 1;
+            y * 2
+        },
+        Op::Move => move |x| {
+            println!("{}", captured);
+            x
+        },
+        Op::Empty => || {},
+    };
+}
@@ -0,0 +1,24 @@
+// A `match` passed as a function argument.
+
+fn main() {
+    foo(match x {
+        Some(y) => y,
+        None => 0,
+    });
+    foo(a, b, match x {
+        Some(y) => y,
+        None => 0,
+    });
+    foo(
+        match x {
+            Some(y) => y,
+            None => 0,
+        },
+        b,
+    );
+    self.consume(match kind {
+        Kind::Short => 1,
+        Kind::Medium => 2,
+        Kind::Long => 3,
+    });
+}
@@ -0,0 +1,31 @@
+// A nested match as an arm body
+
+fn foo(x: Option<Result<u32, String>>) -> u32 {
+    match x {
+        Some(r) => match r {
+            Ok(n) => n,
+            Err(e) => e.len() as u32,
+        },
+        None => 0,
+    }
+}
+
+fn bar(a: u8, b: u8) {
+    match a {
+        0 => match b {
+            0 => println!("both zero"),
+            _ => println!("only a is zero"),
+        },
+        1 => match b {
+            1 => println!("both one"),
+            _ => {}
+        },
+        _ => match b {
+            0 => match a.checked_add(b) {
+                Some(c) => println!("{}", c),
+                None => {}
+            },
+            _ => {}
+        },
+    }
+}
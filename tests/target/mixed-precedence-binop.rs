@@ -0,0 +1,13 @@
+// Wrapped binary expressions with operators of different precedence.
+
+fn main() {
+    let x = a + b * c - d / e;
+
+    let total = first_operand_value
+        + second_operand_value * multiplier_value
+        - third_operand_value / divisor_value
+        + fourth_operand_value * another_multiplier;
+
+    let is_valid = first_condition_value && second_value == expected_second_value
+        || third_value < maximum_third_value && !is_disabled;
+}
@@ -0,0 +1,20 @@
+// Single-element tuples keep their trailing comma; parenthesized expressions don't gain one
+
+fn main() {
+    let paren = (x);
+    let tuple = (x,);
+    let nested = ((x,),);
+    let unit = ();
+    let sum = (a + b) * (c,).0;
+
+    let long_tuple = (some_function_with_a_long_name(
+        first_argument,
+        second_argument,
+        third_arg,
+    ),);
+    let long_paren = (some_function_with_a_long_name(first_argument, second_argument, third_arg));
+
+    let (a,) = (1,);
+    let t: (u32,) = (1u32,);
+    foo((x,), (y));
+}
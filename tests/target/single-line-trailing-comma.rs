@@ -0,0 +1,24 @@
+// Stray trailing commas are removed from single-line lists and kept in multi-line ones.
+// Macro arguments keep theirs, since not every macro accepts one.
+
+fn main() {
+    let a = [1, 2, 3];
+    let b = foo(a, b);
+    let c = (1, 2);
+    let d = (1,);
+    let e = vec![1, 2, 3];
+    let f = Foo { a: 1, b: 2 };
+    let g: Foo<A, B> = bar::<A, B>();
+    let h = [
+        first_element,
+        second_element,
+        third_element,
+        fourth_element,
+        fifth,
+    ];
+    foo!(a, b,);
+    match x {
+        Foo(a, b) => {}
+    }
+}
+fn f(a: u32, b: u32) {}
@@ -0,0 +1,29 @@
+// Async and const methods in traits and impls.
+
+trait Foo {
+    async fn foo(&self) -> u32 {
+        42
+    }
+    async fn bar(
+        &self,
+        first_argument: SomeLongTypeName,
+        second_argument: AnotherLongTypeName,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+    const fn baz() -> u32;
+    async unsafe fn qux(&self);
+}
+
+impl Foo for Bar {
+    async fn foo(&self) -> u32 {
+        let x = 1;
+        x + 41
+    }
+    const fn new() -> Self {
+        Bar
+    }
+    pub(crate) const unsafe fn raw(&self) -> *const u8 {
+        self.ptr
+    }
+}
@@ -0,0 +1,12 @@
+// Default associated consts in trait definitions.
+
+trait Foo {
+    const A: u32;
+    const B: u32 = 5;
+    const C: &'static str = "c";
+    const LONG: u64 =
+        FIRST_COMPONENT_VALUE * SECOND_COMPONENT_VALUE + THIRD_COMPONENT_VALUE + FOURTH;
+}
+
+const LONG: u64 =
+    FIRST_COMPONENT_VALUE * SECOND_COMPONENT_VALUE + THIRD_COMPONENT_VALUE + FOURTH_VALUE;
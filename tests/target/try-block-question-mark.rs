@@ -0,0 +1,22 @@
+// `?` expressions and chains inside `try` blocks.
+#![feature(try_blocks)]
+
+fn main() {
+    let x: Result<u32, Error> = try {
+        let a = foo()?;
+        a + bar()?
+    };
+    let y: Option<u32> = try {
+        some_object
+            .first_method()?
+            .second_method(argument)?
+            .third_method()?
+            .fourth_method()?
+    };
+    let z: Result<(), E> = try {};
+    let w: Result<u32, Error> = try {
+        // A comment.
+        foo()?
+    };
+    let v = async { try_thing().await? };
+}
@@ -0,0 +1,30 @@
+#![feature(const_generics, const_evaluatable_checked)]
+
+// Where predicates on const-generic array lengths.
+
+fn foo<const N: usize>()
+where
+    [(); N]: Sized,
+{
+}
+
+fn bar<const N: usize, const M: usize>() -> [u8; N + M]
+where
+    [(); N + M]: Sized,
+    [(); { N * 2 }]: Sized,
+{
+    [0; N + M]
+}
+
+struct Buf<const N: usize>
+where
+    [u8; N - 1]: Sized,
+{
+    data: [u8; N],
+}
+
+impl<const N: usize> Buf<N>
+where
+    [(); core::mem::size_of::<[u8; N]>() + SOME_HEADER_SIZE_CONSTANT]: Sized,
+{
+}
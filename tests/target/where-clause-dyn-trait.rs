@@ -0,0 +1,30 @@
+// Trait objects in where clause predicates.
+
+fn foo<T>(t: T)
+where
+    T: AsRef<dyn Trait>,
+{
+}
+
+fn bar<T, U>(t: T, u: U)
+where
+    T: AsRef<dyn Trait + Send>,
+    U: Fn(&dyn Any) -> Box<dyn Error>,
+{
+}
+
+fn baz<T>(t: T)
+where
+    T: AsRef<dyn SomeLongTraitName + Send + Sync>
+        + Into<Box<dyn std::error::Error + Send + Sync + 'static>>
+        + Clone
+        + 'static,
+{
+}
+
+struct Foo<T>
+where
+    T: Deref<Target = dyn Trait>,
+{
+    t: T,
+}
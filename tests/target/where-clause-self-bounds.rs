@@ -0,0 +1,73 @@
+// Where clauses with predicates on `Self`.
+
+trait Foo
+where
+    Self: Sized + Clone + Debug,
+{
+    fn foo(&self)
+    where
+        Self: Sized;
+    fn bar(&self)
+    where
+        Self: Sized + Clone + Debug + Send + Sync + PartialEq + Eq + Hash + 'static;
+}
+
+impl<T> Bar for T
+where
+    Self: Sized + Clone + Debug + Send + Sync + PartialEq + Eq + Hash + 'static,
+    T: Copy,
+{
+    fn baz(self) -> Self
+    where
+        Self: Sized + Clone + std::fmt::Debug + Send + Sync + PartialOrd + Ord,
+    {
+        self
+    }
+}
+
+fn f<T>()
+where
+    for<'a> &'a Self: IntoIterator<Item = &'a T> + Clone + Send + Sync + 'static + Sized,
+{
+}
+
+trait Baz {
+    fn bar(&self)
+    where
+        Self: Sized
+            + Clone
+            + Debug
+            + Send
+            + Sync
+            + PartialEq
+            + Eq
+            + Hash
+            + PartialOrd
+            + Ord
+            + Default
+            + 'static;
+}
+
+impl<T> Qux for T
+where
+    Self: Sized
+        + Clone
+        + Debug
+        + Send
+        + Sync
+        + PartialEq
+        + Eq
+        + Hash
+        + PartialOrd
+        + Ord
+        + Default
+        + 'static,
+{
+    fn baz(self) -> Self
+    where
+        Self:
+            Sized + Clone + std::fmt::Debug + Send + Sync + PartialOrd + Ord + Iterator<Item = u32>,
+    {
+        self
+    }
+}